@@ -0,0 +1,279 @@
+use crate::cache::models::{BlockChoice, BlockResolution, ConflictBlock};
+
+/// A piece of a conflicted file: plain text, or a conflict block together
+/// with its raw text so unresolved blocks can be written back untouched.
+enum Segment {
+    Text(String),
+    Conflict { block: ConflictBlock, raw: String },
+}
+
+/// If `line` is a conflict marker made of `marker`, return its label.
+/// Markers are exactly seven characters, optionally followed by a space
+/// and a label.
+fn marker_label(line: &str, marker: &str) -> Option<String> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let rest = line.strip_prefix(marker)?;
+    if rest.is_empty() {
+        Some(String::new())
+    } else {
+        rest.strip_prefix(' ').map(str::to_string)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Ours,
+    Base,
+    Theirs,
+}
+
+fn split_segments(content: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    // Block under construction and which section we're in
+    let mut current: Option<(ConflictBlock, String, Section)> = None;
+    let mut index = 0usize;
+
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        let line_no = i + 1;
+
+        let Some((block, raw, section)) = current.as_mut() else {
+            if let Some(label) = marker_label(line, "<<<<<<<") {
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                current = Some((
+                    ConflictBlock {
+                        index,
+                        start_line: line_no,
+                        end_line: line_no,
+                        ours_label: label,
+                        base_label: None,
+                        theirs_label: String::new(),
+                        ours: String::new(),
+                        base: None,
+                        theirs: String::new(),
+                    },
+                    line.to_string(),
+                    Section::Ours,
+                ));
+            } else {
+                text.push_str(line);
+            }
+            continue;
+        };
+
+        // A new opening marker before the block closed: the previous one
+        // was malformed, keep it as plain text and start over.
+        if let Some(label) = marker_label(line, "<<<<<<<") {
+            text.push_str(raw);
+            segments.push(Segment::Text(std::mem::take(&mut text)));
+            block.start_line = line_no;
+            block.ours_label = label;
+            block.base_label = None;
+            block.ours.clear();
+            block.base = None;
+            block.theirs.clear();
+            *raw = line.to_string();
+            *section = Section::Ours;
+            continue;
+        }
+
+        raw.push_str(line);
+        match *section {
+            Section::Ours => {
+                if let Some(label) = marker_label(line, "|||||||") {
+                    block.base_label = Some(label);
+                    block.base = Some(String::new());
+                    *section = Section::Base;
+                } else if marker_label(line, "=======").is_some() {
+                    *section = Section::Theirs;
+                } else {
+                    block.ours.push_str(line);
+                }
+            }
+            Section::Base => {
+                if marker_label(line, "=======").is_some() {
+                    *section = Section::Theirs;
+                } else if let Some(base) = block.base.as_mut() {
+                    base.push_str(line);
+                }
+            }
+            Section::Theirs => {
+                if let Some(label) = marker_label(line, ">>>>>>>") {
+                    block.end_line = line_no;
+                    block.theirs_label = label;
+                    let (block, raw, _) = current.take().unwrap();
+                    segments.push(Segment::Conflict { block, raw });
+                    index += 1;
+                } else {
+                    block.theirs.push_str(line);
+                }
+            }
+        }
+    }
+
+    // Unterminated block: not a conflict after all
+    if let Some((_, raw, _)) = current {
+        text.push_str(&raw);
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    segments
+}
+
+/// Parse the conflict blocks of a file with merge conflict markers,
+/// including diff3-style `|||||||` base sections.
+pub fn parse_conflicts(content: &str) -> Vec<ConflictBlock> {
+    split_segments(content)
+        .into_iter()
+        .filter_map(|s| match s {
+            Segment::Conflict { block, .. } => Some(block),
+            Segment::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Rewrite `content` with the given per-block resolutions applied. Blocks
+/// without a resolution keep their markers.
+pub fn resolve_conflicts(content: &str, resolutions: &[BlockResolution]) -> Result<String, String> {
+    let segments = split_segments(content);
+    let block_count = segments
+        .iter()
+        .filter(|s| matches!(s, Segment::Conflict { .. }))
+        .count();
+    if let Some(r) = resolutions.iter().find(|r| r.block_index >= block_count) {
+        return Err(format!(
+            "Conflict block {} does not exist (file has {})",
+            r.block_index, block_count
+        ));
+    }
+
+    let mut out = String::with_capacity(content.len());
+    for segment in segments {
+        let (block, raw) = match segment {
+            Segment::Text(text) => {
+                out.push_str(&text);
+                continue;
+            }
+            Segment::Conflict { block, raw } => (block, raw),
+        };
+        let Some(resolution) = resolutions.iter().find(|r| r.block_index == block.index) else {
+            out.push_str(&raw);
+            continue;
+        };
+        match resolution.choice {
+            BlockChoice::Ours => out.push_str(&block.ours),
+            BlockChoice::Theirs => out.push_str(&block.theirs),
+            BlockChoice::Base => match block.base {
+                Some(ref base) => out.push_str(base),
+                None => {
+                    return Err(format!(
+                        "Conflict block {} has no base section (use merge.conflictStyle=diff3)",
+                        block.index
+                    ))
+                }
+            },
+            BlockChoice::OursThenTheirs => {
+                out.push_str(&block.ours);
+                out.push_str(&block.theirs);
+            }
+            BlockChoice::TheirsThenOurs => {
+                out.push_str(&block.theirs);
+                out.push_str(&block.ours);
+            }
+            BlockChoice::Custom => {
+                let Some(ref text) = resolution.content else {
+                    return Err(format!(
+                        "Custom resolution for block {} has no content",
+                        block.index
+                    ));
+                };
+                out.push_str(text);
+                // Keep the following line from being glued onto the edit
+                if !text.is_empty() && !text.ends_with('\n') && raw.ends_with('\n') {
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF3: &str = "fn main() {\n\
+<<<<<<< HEAD\n\
+\tprintln!(\"ours\");\n\
+||||||| merged common ancestors\n\
+\tprintln!(\"base\");\n\
+=======\n\
+\tprintln!(\"theirs\");\n\
+>>>>>>> feature\n\
+}\n\
+<<<<<<< HEAD\n\
+a\n\
+=======\n\
+b\n\
+>>>>>>> feature\n";
+
+    #[test]
+    fn test_parse_diff3_and_merge_styles() {
+        let blocks = parse_conflicts(DIFF3);
+        assert_eq!(blocks.len(), 2);
+
+        let first = &blocks[0];
+        assert_eq!((first.start_line, first.end_line), (2, 8));
+        assert_eq!(first.ours_label, "HEAD");
+        assert_eq!(first.theirs_label, "feature");
+        assert_eq!(first.base_label.as_deref(), Some("merged common ancestors"));
+        assert_eq!(first.ours, "\tprintln!(\"ours\");\n");
+        assert_eq!(first.base.as_deref(), Some("\tprintln!(\"base\");\n"));
+        assert_eq!(first.theirs, "\tprintln!(\"theirs\");\n");
+
+        let second = &blocks[1];
+        assert_eq!(second.index, 1);
+        assert_eq!((second.start_line, second.end_line), (10, 14));
+        assert!(second.base.is_none());
+        assert_eq!(
+            (second.ours.as_str(), second.theirs.as_str()),
+            ("a\n", "b\n")
+        );
+    }
+
+    #[test]
+    fn test_resolve_single_block_keeps_others() {
+        let resolved = resolve_conflicts(
+            DIFF3,
+            &[BlockResolution {
+                block_index: 0,
+                choice: BlockChoice::Base,
+                content: None,
+            }],
+        )
+        .unwrap();
+        assert!(resolved.starts_with("fn main() {\n\tprintln!(\"base\");\n}\n<<<<<<< HEAD\n"));
+        assert_eq!(parse_conflicts(&resolved).len(), 1);
+
+        let resolved = resolve_conflicts(
+            &resolved,
+            &[BlockResolution {
+                block_index: 0,
+                choice: BlockChoice::Custom,
+                content: Some("c".into()),
+            }],
+        )
+        .unwrap();
+        assert_eq!(resolved, "fn main() {\n\tprintln!(\"base\");\n}\nc\n");
+    }
+
+    #[test]
+    fn test_unterminated_block_is_plain_text() {
+        let text = "<<<<<<< HEAD\nx\n=======\ny\n";
+        assert!(parse_conflicts(text).is_empty());
+        assert_eq!(resolve_conflicts(text, &[]).unwrap(), text);
+    }
+}
@@ -6,6 +6,12 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Bumped whenever `CacheEntry` or the parsed diff models change shape;
+/// bincode data isn't self-describing, so old entries can't be read back.
+/// v2: diff header metadata. v3: per-line data in parsed hunks.
+/// v4: content fingerprints instead of file mtimes.
+const DISK_FORMAT_VERSION: u32 = 4;
+
 #[derive(Default)]
 struct CacheCounters {
     memory_hits: AtomicU64,
@@ -80,7 +86,7 @@ impl DiffCache {
         };
 
         base.push("EasyGit");
-        base.push("diff-cache");
+        base.push(format!("diff-cache-v{}", DISK_FORMAT_VERSION));
         base
     }
 
@@ -213,97 +219,75 @@ impl DiffCache {
         self.evict_memory_if_needed();
     }
 
-    /// Get file modification time in seconds since epoch
-    fn get_file_mtime(repo_path: &str, file_path: &str) -> Option<u64> {
-        let full_path = std::path::Path::new(repo_path).join(file_path);
-        let metadata = std::fs::metadata(&full_path).ok()?;
-        let mtime = metadata.modified().ok()?;
-        Some(mtime.duration_since(UNIX_EPOCH).ok()?.as_secs())
+    /// Key for a diff between commits. `range` names both ends by object ID,
+    /// so these entries are shared between repositories and never go stale,
+    /// provided the caller pins every config-dependent output option.
+    /// An empty `file_path` stands for the whole diff.
+    fn make_commit_key(range: &str, file_path: &str) -> String {
+        format!("commit:{}:{}", range, file_path)
     }
 
-    /// Check if cached entry is stale based on file modification time
-    fn is_stale(&self, entry: &CacheEntry, repo_path: &str, file_path: &str) -> bool {
-        let Some(cached_mtime) = entry.file_mtime else {
-            // Old cache entries without mtime — treat as potentially stale
-            return false; // Keep for now, will get mtime on next update
-        };
-
-        let Some(current_mtime) = Self::get_file_mtime(repo_path, file_path) else {
-            // File doesn't exist or can't be read — assume stale
-            return true;
-        };
-
-        current_mtime != cached_mtime
+    fn drop_entry(&self, key: &str) {
+        self.memory.remove(key);
+        if let Ok(mut order) = self.access_order.lock() {
+            order.shift_remove(key);
+        }
+        if let Some(db) = &self.disk {
+            let _ = db.remove(key.as_bytes());
+        }
     }
 
-    /// Get a cached diff, updating access metadata
-    pub fn get(&self, repo_path: &str, file_path: &str, staged: bool) -> Option<CacheEntry> {
-        let key = Self::make_key(repo_path, file_path, staged);
-
-        if let Some(mut entry) = self.memory.get_mut(&key) {
-            // Check if cached data is stale based on file mtime
-            if self.is_stale(&entry, repo_path, file_path) {
+    /// Look up `key`, treating an entry recorded for different content
+    /// (fingerprint mismatch) as a miss and dropping it.
+    fn get_by_key(&self, key: &str, fingerprint: &str) -> Option<CacheEntry> {
+        if let Some(mut entry) = self.memory.get_mut(key) {
+            if entry.fingerprint.as_deref() != Some(fingerprint) {
                 drop(entry); // Release the lock
-                self.memory.remove(&key);
-                if let Ok(mut order) = self.access_order.lock() {
-                    order.shift_remove(&key);
-                }
+                self.drop_entry(key);
                 self.counters.misses.fetch_add(1, Ordering::Relaxed);
-                return None; // Cache miss due to staleness
+                return None;
             }
 
             self.counters.memory_hits.fetch_add(1, Ordering::Relaxed);
             // Update access metadata
             entry.access_count = entry.access_count.saturating_add(1);
             entry.timestamp = Self::current_timestamp();
-            self.record_access_order(&key);
-            self.write_disk_entry(&key, &entry.clone());
+            self.record_access_order(key);
+            self.write_disk_entry(key, &entry.clone());
 
             Some(entry.clone())
         } else {
-            let Some(mut disk_entry) = self.read_disk_entry(&key) else {
+            let Some(mut disk_entry) = self.read_disk_entry(key) else {
                 self.counters.misses.fetch_add(1, Ordering::Relaxed);
                 return None;
             };
 
-            // Check if disk-cached data is stale
-            if self.is_stale(&disk_entry, repo_path, file_path) {
-                if let Some(db) = &self.disk {
-                    let _ = db.remove(key.as_bytes());
-                }
+            if disk_entry.fingerprint.as_deref() != Some(fingerprint) {
+                self.drop_entry(key);
                 self.counters.misses.fetch_add(1, Ordering::Relaxed);
-                return None; // Cache miss due to staleness
+                return None;
             }
 
             self.counters.disk_hits.fetch_add(1, Ordering::Relaxed);
             disk_entry.access_count = disk_entry.access_count.saturating_add(1);
             disk_entry.timestamp = Self::current_timestamp();
 
-            self.insert_memory_entry_by_key(key.clone(), disk_entry.clone());
-            self.write_disk_entry(&key, &disk_entry);
+            self.insert_memory_entry_by_key(key.to_string(), disk_entry.clone());
+            self.write_disk_entry(key, &disk_entry);
 
             Some(disk_entry)
         }
     }
 
-    /// Store a diff in cache without parsed payload.
-    pub fn set(&self, repo_path: &str, file_path: &str, staged: bool, diff_text: String) {
-        self.set_with_parsed(repo_path, file_path, staged, diff_text, None);
-    }
-
-    /// Store a diff in cache with optional parsed payload.
-    pub fn set_with_parsed(
+    fn set_by_key(
         &self,
-        repo_path: &str,
+        key: String,
         file_path: &str,
-        staged: bool,
+        fingerprint: &str,
         diff_text: String,
         parsed: Option<ParsedDiff>,
     ) {
-        let key = Self::make_key(repo_path, file_path, staged);
         let size_bytes = diff_text.len();
-        let file_mtime = Self::get_file_mtime(repo_path, file_path);
-
         let entry = CacheEntry {
             file_path: file_path.to_string(),
             diff_text,
@@ -311,13 +295,71 @@ impl DiffCache {
             timestamp: Self::current_timestamp(),
             access_count: 0,
             size_bytes,
-            file_mtime,
+            fingerprint: Some(fingerprint.to_string()),
         };
 
         self.insert_memory_entry_by_key(key.clone(), entry.clone());
         self.write_disk_entry(&key, &entry);
     }
 
+    /// Get a cached diff, updating access metadata. `fingerprint` identifies
+    /// the content being diffed (see `diff_fingerprints`); an entry stored
+    /// for other content is a miss.
+    pub fn get(
+        &self,
+        repo_path: &str,
+        file_path: &str,
+        staged: bool,
+        fingerprint: &str,
+    ) -> Option<CacheEntry> {
+        let key = Self::make_key(repo_path, file_path, staged);
+        self.get_by_key(&key, fingerprint)
+    }
+
+    /// Store a diff in cache without parsed payload.
+    pub fn set(
+        &self,
+        repo_path: &str,
+        file_path: &str,
+        staged: bool,
+        fingerprint: &str,
+        diff_text: String,
+    ) {
+        self.set_with_parsed(repo_path, file_path, staged, fingerprint, diff_text, None);
+    }
+
+    /// Store a diff in cache with optional parsed payload.
+    pub fn set_with_parsed(
+        &self,
+        repo_path: &str,
+        file_path: &str,
+        staged: bool,
+        fingerprint: &str,
+        diff_text: String,
+        parsed: Option<ParsedDiff>,
+    ) {
+        let key = Self::make_key(repo_path, file_path, staged);
+        self.set_by_key(key, file_path, fingerprint, diff_text, parsed);
+    }
+
+    /// Get a cached commit diff. `range` is a commit's full object ID or
+    /// `<base oid>..<target oid>`; `file_path` is "" for the whole diff.
+    pub fn get_commit(&self, range: &str, file_path: &str) -> Option<CacheEntry> {
+        self.get_by_key(&Self::make_commit_key(range, file_path), range)
+    }
+
+    /// Cache a commit diff; see [`DiffCache::get_commit`].
+    pub fn set_commit(
+        &self,
+        range: &str,
+        file_path: &str,
+        diff_text: String,
+        parsed: Option<ParsedDiff>,
+    ) {
+        let key = Self::make_commit_key(range, file_path);
+        self.set_by_key(key, file_path, range, diff_text, parsed);
+    }
+
     /// Remove a specific cache entry
     pub fn invalidate(&self, repo_path: &str, file_path: &str, staged: bool) {
         let key = Self::make_key(repo_path, file_path, staged);
@@ -328,6 +370,50 @@ impl DiffCache {
         }
     }
 
+    /// Drop every entry of a repository, in memory and on disk, for which
+    /// `pred(file_path, staged)` holds. Returns how many keys were removed.
+    pub fn invalidate_where(&self, repo_path: &str, pred: impl Fn(&str, bool) -> bool) -> usize {
+        let prefix = format!("{}:", repo_path);
+        let matches = |key: &str| {
+            let Some((file_path, side)) = key
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.rsplit_once(':'))
+            else {
+                return false;
+            };
+            pred(file_path, side == "staged")
+        };
+
+        let mut removed: Vec<String> = self
+            .memory
+            .iter()
+            .map(|entry| entry.key().clone())
+            .filter(|key| matches(key))
+            .collect();
+        for key in &removed {
+            self.memory.remove(key);
+        }
+        if let Ok(mut order) = self.access_order.lock() {
+            order.retain(|k, _| !matches(k));
+        }
+
+        if let Some(db) = &self.disk {
+            for key in db
+                .scan_prefix(prefix.as_bytes())
+                .keys()
+                .filter_map(Result::ok)
+                .filter_map(|k| String::from_utf8(k.to_vec()).ok())
+                .filter(|key| matches(key))
+            {
+                let _ = db.remove(key.as_bytes());
+                if !removed.contains(&key) {
+                    removed.push(key);
+                }
+            }
+        }
+        removed.len()
+    }
+
     /// Clear all cache entries for a repository
     pub fn clear_repo(&self, repo_path: &str) {
         let prefix = format!("{}:", repo_path);
@@ -453,17 +539,43 @@ mod tests {
     #[test]
     fn test_cache_set_get() {
         let cache = DiffCache::default_instance();
-        cache.set("/repo", "file.txt", false, "diff content".to_string());
+        cache.set("/repo", "file.txt", false, "fp", "diff content".to_string());
 
-        let result = cache.get("/repo", "file.txt", false);
+        let result = cache.get("/repo", "file.txt", false, "fp");
         assert!(result.is_some());
         assert_eq!(result.unwrap().diff_text, "diff content");
     }
 
+    #[test]
+    fn test_fingerprint_mismatch_is_miss() {
+        let cache = DiffCache::default_instance();
+        cache.set("/repo", "file.txt", true, "head1 idx1", "old".to_string());
+        assert!(cache.get("/repo", "file.txt", true, "head1 idx2").is_none());
+        // The outdated entry is gone, even for its own fingerprint
+        assert!(cache.get("/repo", "file.txt", true, "head1 idx1").is_none());
+    }
+
+    #[test]
+    fn test_commit_entries_outlive_repo_clear() {
+        let cache = DiffCache::default_instance();
+        cache.set_commit("abc123", "", "commit diff".to_string(), None);
+        cache.set_commit("abc..def", "a.rs", "file diff".to_string(), None);
+        assert_eq!(
+            cache.get_commit("abc123", "").unwrap().diff_text,
+            "commit diff"
+        );
+        cache.clear_repo("/repo");
+        assert_eq!(
+            cache.get_commit("abc..def", "a.rs").unwrap().diff_text,
+            "file diff"
+        );
+        assert!(cache.get_commit("abc..def", "b.rs").is_none());
+    }
+
     #[test]
     fn test_cache_miss() {
         let cache = DiffCache::default_instance();
-        let result = cache.get("/repo", "nonexistent.txt", false);
+        let result = cache.get("/repo", "nonexistent.txt", false, "fp");
         assert!(result.is_none());
     }
 
@@ -476,14 +588,29 @@ mod tests {
         let cache = DiffCache::new(config);
 
         // Fill cache to capacity
-        cache.set("/repo", "f1.txt", false, "content1".to_string());
-        cache.set("/repo", "f2.txt", false, "content2".to_string());
-        cache.set("/repo", "f3.txt", false, "content3".to_string());
+        cache.set("/repo", "f1.txt", false, "fp", "content1".to_string());
+        cache.set("/repo", "f2.txt", false, "fp", "content2".to_string());
+        cache.set("/repo", "f3.txt", false, "fp", "content3".to_string());
         assert_eq!(cache.memory.len(), 3);
 
         // Adding one more should evict oldest
-        cache.set("/repo", "f4.txt", false, "content4".to_string());
+        cache.set("/repo", "f4.txt", false, "fp", "content4".to_string());
         assert_eq!(cache.memory.len(), 3);
-        assert!(cache.get("/repo", "f1.txt", false).is_none()); // f1 was oldest
+        assert!(cache.get("/repo", "f1.txt", false, "fp").is_none()); // f1 was oldest
+    }
+
+    #[test]
+    fn test_invalidate_where() {
+        let cache = DiffCache::default_instance();
+        cache.set("/repo", "src/a.rs", false, "fp", "a".to_string());
+        cache.set("/repo", "src/a.rs", true, "fp", "a staged".to_string());
+        cache.set("/repo", "b.rs", true, "fp", "b staged".to_string());
+        cache.set("/other", "src/a.rs", false, "fp", "other".to_string());
+
+        assert_eq!(cache.invalidate_where("/repo", |_, staged| staged), 2);
+        assert!(cache.get("/repo", "src/a.rs", true, "fp").is_none());
+        assert!(cache.get("/repo", "b.rs", true, "fp").is_none());
+        assert!(cache.get("/repo", "src/a.rs", false, "fp").is_some());
+        assert!(cache.get("/other", "src/a.rs", false, "fp").is_some());
     }
 }
@@ -1,71 +1,360 @@
-use crate::cache::models::{ParsedDiff, ParsedFileDiff, ParsedHunk};
+use crate::cache::models::{
+    DiffLine, DiffLineKind, FileChangeKind, ParsedDiff, ParsedFileDiff, ParsedHunk,
+};
 use rayon::prelude::*;
 
+/// Split `text` into segments that each begin with a line starting with
+/// `marker`. Anything before the first marker is returned as the first
+/// segment so callers can treat it as a preamble.
+fn split_at_line_prefix<'a>(text: &'a str, marker: &str) -> Vec<&'a str> {
+    let mut starts = Vec::new();
+    let mut offset = 0usize;
+    for line in text.split_inclusive('\n') {
+        if line.starts_with(marker) {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+
+    let mut segments = Vec::with_capacity(starts.len() + 1);
+    let mut prev = 0usize;
+    for start in starts {
+        if start > prev {
+            segments.push(&text[prev..start]);
+        }
+        prev = start;
+    }
+    if prev < text.len() {
+        segments.push(&text[prev..]);
+    }
+    segments
+}
+
+/// Parse `-12,3` / `+7` style ranges from a hunk header into (start, count).
+fn parse_range(range: &str) -> (u32, u32) {
+    let mut parts = range[1..].splitn(2, ',');
+    let start = parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+    let count = parts.next().and_then(|s| s.parse().ok()).unwrap_or(1);
+    (start, count)
+}
+
+/// Parse `@@ -a,b +c,d @@ section` into (old_start, old_lines, new_start, new_lines).
+fn parse_hunk_header(header: &str) -> (u32, u32, u32, u32) {
+    let mut old = (0, 0);
+    let mut new = (0, 0);
+    for token in header.split_whitespace().skip(1) {
+        if token.starts_with('-') {
+            old = parse_range(token);
+        } else if token.starts_with('+') {
+            new = parse_range(token);
+        } else if token == "@@" {
+            break;
+        }
+    }
+    (old.0, old.1, new.0, new.1)
+}
+
 fn parse_hunk_lines(hunk_text: &str) -> ParsedHunk {
-    let mut header = String::new();
+    let mut raw_lines = hunk_text.lines();
+    let header = raw_lines.next().unwrap_or_default().to_string();
+    let (old_start, old_lines, new_start, new_lines) = parse_hunk_header(&header);
+
+    let mut lines: Vec<DiffLine> = Vec::new();
     let mut added_lines = 0usize;
     let mut removed_lines = 0usize;
     let mut context_lines = 0usize;
+    let mut old_line = old_start;
+    let mut new_line = new_start;
 
-    for (idx, line) in hunk_text.lines().enumerate() {
-        if idx == 0 && line.starts_with("@@") {
-            header = line.to_string();
+    for line in raw_lines {
+        let (kind, content) = if let Some(rest) = line.strip_prefix('+') {
+            (DiffLineKind::Added, rest)
+        } else if let Some(rest) = line.strip_prefix('-') {
+            (DiffLineKind::Removed, rest)
+        } else if line.starts_with('\\') {
+            // "\ No newline at end of file" applies to the preceding line
+            if let Some(last) = lines.last_mut() {
+                last.no_newline = true;
+            }
             continue;
-        }
-
-        if line.starts_with('+') && !line.starts_with("+++") {
-            added_lines += 1;
-        } else if line.starts_with('-') && !line.starts_with("---") {
-            removed_lines += 1;
         } else {
-            context_lines += 1;
-        }
+            (
+                DiffLineKind::Context,
+                line.strip_prefix(' ').unwrap_or(line),
+            )
+        };
+
+        let entry = match kind {
+            DiffLineKind::Added => {
+                added_lines += 1;
+                new_line += 1;
+                DiffLine {
+                    kind,
+                    old_line: None,
+                    new_line: Some(new_line - 1),
+                    content: content.to_string(),
+                    no_newline: false,
+                }
+            }
+            DiffLineKind::Removed => {
+                removed_lines += 1;
+                old_line += 1;
+                DiffLine {
+                    kind,
+                    old_line: Some(old_line - 1),
+                    new_line: None,
+                    content: content.to_string(),
+                    no_newline: false,
+                }
+            }
+            DiffLineKind::Context => {
+                context_lines += 1;
+                old_line += 1;
+                new_line += 1;
+                DiffLine {
+                    kind,
+                    old_line: Some(old_line - 1),
+                    new_line: Some(new_line - 1),
+                    content: content.to_string(),
+                    no_newline: false,
+                }
+            }
+        };
+        lines.push(entry);
     }
 
     ParsedHunk {
         header,
+        old_start,
+        old_lines,
+        new_start,
+        new_lines,
         added_lines,
         removed_lines,
         context_lines,
+        lines,
     }
 }
 
-fn parse_file_chunk(file_chunk: &str) -> ParsedFileDiff {
-    let mut file_path = String::new();
+/// Decode a path git wrote in C-style quotes (`"tab\t\303\251.txt"`);
+/// octal escapes are raw bytes of a UTF-8 name. Unquoted paths are
+/// returned as is.
+fn unquote_path(raw: &str) -> String {
+    let Some(inner) = raw
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        return raw.to_string();
+    };
+    let mut bytes = Vec::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0u8; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some('r') => bytes.push(b'\r'),
+            Some('a') => bytes.push(0x07),
+            Some('b') => bytes.push(0x08),
+            Some('f') => bytes.push(0x0c),
+            Some('v') => bytes.push(0x0b),
+            Some(d @ '0'..='7') => {
+                let mut value = d.to_digit(8).unwrap_or(0);
+                for _ in 0..2 {
+                    let next = chars.clone().next().and_then(|c| c.to_digit(8));
+                    if let Some(digit) = next {
+                        value = value * 8 + digit;
+                        chars.next();
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some(other) => {
+                let mut buf = [0u8; 4];
+                bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+            }
+            None => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
 
-    for line in file_chunk.lines().take(8) {
-        if let Some(rest) = line.strip_prefix("+++ b/") {
-            file_path = rest.to_string();
-            break;
+/// Strip the `a/`/`b/` (or mnemonic `c/`, `i/`, `w/`, `o/`) prefix from an
+/// unquoted-or-quoted path on a `---`/`+++` or `diff --git` line. None for
+/// `/dev/null`.
+fn side_path(raw: &str) -> Option<String> {
+    // git appends a tab to unquoted names containing spaces
+    let raw = raw.trim_end_matches('\t');
+    if raw == "/dev/null" {
+        return None;
+    }
+    let path = unquote_path(raw);
+    let stripped = match path.as_bytes() {
+        [b'a' | b'b' | b'c' | b'i' | b'w' | b'o', b'/', ..] => path[2..].to_string(),
+        _ => path,
+    };
+    Some(stripped)
+}
+
+/// Both paths from `diff --git <a> <b>`, for headers without `---`/`+++`
+/// lines (binary files, pure renames and mode changes). Unquoted names
+/// containing spaces are only unambiguous when both sides match.
+fn parse_git_line(rest: &str) -> (Option<String>, Option<String>) {
+    if rest.starts_with('"') {
+        // Find the closing quote of the first name, skipping escapes
+        let mut escaped = false;
+        let end = rest.char_indices().skip(1).find_map(|(i, c)| match c {
+            _ if escaped => {
+                escaped = false;
+                None
+            }
+            '\\' => {
+                escaped = true;
+                None
+            }
+            '"' => Some(i + 1),
+            _ => None,
+        });
+        let Some(end) = end else {
+            return (None, None);
+        };
+        let (old, new) = rest.split_at(end);
+        return (side_path(old), side_path(new.trim_start()));
+    }
+    if let Some(pos) = rest.find(" \"") {
+        return (side_path(&rest[..pos]), side_path(&rest[pos + 1..]));
+    }
+    // "a/<p> b/<p>": the separator sits right in the middle
+    let half = rest.len().saturating_sub(1) / 2;
+    if rest.len() % 2 == 1 && rest.is_char_boundary(half) && rest[half..].starts_with(' ') {
+        let (old, new) = (&rest[..half], &rest[half + 1..]);
+        if old.get(2..) == new.get(2..) {
+            return (side_path(old), side_path(new));
         }
     }
+    match rest.split_once(' ') {
+        Some((old, new)) => (side_path(old), side_path(new)),
+        None => (None, None),
+    }
+}
 
-    if file_path.is_empty() {
-        file_path = "unknown".to_string();
+fn parse_file_chunk(file_chunk: &str) -> ParsedFileDiff {
+    let segments = split_at_line_prefix(file_chunk, "@@ ");
+    let (header, hunks_raw) = match segments.split_first() {
+        Some((first, rest)) if !first.starts_with("@@ ") => (*first, rest.to_vec()),
+        _ => ("", segments),
+    };
+
+    let mut git_paths = (None, None);
+    let mut minus_path = None;
+    let mut plus_path = None;
+    let mut saw_minus_plus = false;
+    let mut rename = (None, None);
+    let mut copy = false;
+    let mut added = false;
+    let mut deleted = false;
+    let mut similarity = None;
+    let mut old_mode = None;
+    let mut new_mode = None;
+    let mut mode_change = false;
+    let mut binary = false;
+
+    for line in header.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            git_paths = parse_git_line(rest);
+        } else if let Some(rest) = line.strip_prefix("--- ") {
+            saw_minus_plus = true;
+            minus_path = side_path(rest);
+        } else if let Some(rest) = line.strip_prefix("+++ ") {
+            saw_minus_plus = true;
+            plus_path = side_path(rest);
+        } else if let Some(mode) = line.strip_prefix("new file mode ") {
+            added = true;
+            new_mode = Some(mode.trim().to_string());
+        } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
+            deleted = true;
+            old_mode = Some(mode.trim().to_string());
+        } else if let Some(mode) = line.strip_prefix("old mode ") {
+            mode_change = true;
+            old_mode = Some(mode.trim().to_string());
+        } else if let Some(mode) = line.strip_prefix("new mode ") {
+            mode_change = true;
+            new_mode = Some(mode.trim().to_string());
+        } else if let Some(pct) = line.strip_prefix("similarity index ") {
+            similarity = pct.trim().trim_end_matches('%').parse().ok();
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            rename.0 = Some(unquote_path(path));
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            rename.1 = Some(unquote_path(path));
+        } else if let Some(path) = line.strip_prefix("copy from ") {
+            copy = true;
+            rename.0 = Some(unquote_path(path));
+        } else if let Some(path) = line.strip_prefix("copy to ") {
+            copy = true;
+            rename.1 = Some(unquote_path(path));
+        } else if let Some(rest) = line.strip_prefix("index ") {
+            // "index abc..def 100644" carries the mode when it didn't change
+            if let Some(mode) = rest.split_whitespace().nth(1) {
+                old_mode.get_or_insert_with(|| mode.to_string());
+                new_mode.get_or_insert_with(|| mode.to_string());
+            }
+        } else if line.starts_with("Binary files ") || line == "GIT binary patch" {
+            binary = true;
+        }
     }
 
-    let hunks_raw: Vec<&str> = file_chunk
-        .split("\n@@")
-        .filter(|segment| !segment.trim().is_empty())
-        .collect();
+    // Explicit rename/copy lines beat ---/+++, which beat the diff --git line
+    let renamed = rename.0.is_some() || rename.1.is_some();
+    let (old_path, new_path) = if renamed {
+        (rename.0.or(git_paths.0), rename.1.or(git_paths.1))
+    } else if saw_minus_plus {
+        (minus_path, plus_path)
+    } else {
+        git_paths
+    };
+    let old_path = if added { None } else { old_path };
+    let new_path = if deleted { None } else { new_path };
 
     let hunks: Vec<ParsedHunk> = hunks_raw
         .par_iter()
-        .map(|segment| {
-            let normalized = if segment.starts_with("@@") {
-                (*segment).to_string()
-            } else {
-                format!("@@{}", segment)
-            };
-            parse_hunk_lines(&normalized)
-        })
+        .map(|segment| parse_hunk_lines(segment))
         .collect();
 
+    let change = if added || (saw_minus_plus && old_path.is_none()) {
+        FileChangeKind::Added
+    } else if deleted || (saw_minus_plus && new_path.is_none()) {
+        FileChangeKind::Deleted
+    } else if copy {
+        FileChangeKind::Copied
+    } else if renamed {
+        FileChangeKind::Renamed
+    } else if mode_change && hunks.is_empty() && !binary {
+        FileChangeKind::ModeChanged
+    } else {
+        FileChangeKind::Modified
+    };
+
+    let file_path = new_path
+        .clone()
+        .or_else(|| old_path.clone())
+        .unwrap_or_else(|| "unknown".to_string());
     let added_lines = hunks.iter().map(|h| h.added_lines).sum();
     let removed_lines = hunks.iter().map(|h| h.removed_lines).sum();
 
     ParsedFileDiff {
         file_path,
+        old_path,
+        new_path,
+        change,
+        similarity,
+        old_mode,
+        new_mode,
+        binary,
+        header: header.to_string(),
         hunks,
         added_lines,
         removed_lines,
@@ -83,9 +372,9 @@ pub fn parse_diff_parallel(diff_text: &str) -> ParsedDiff {
         };
     }
 
-    let files_raw: Vec<&str> = diff_text
-        .split("diff --git ")
-        .filter(|chunk| !chunk.trim().is_empty())
+    let files_raw: Vec<&str> = split_at_line_prefix(diff_text, "diff --git ")
+        .into_iter()
+        .filter(|chunk| chunk.starts_with("diff --git "))
         .collect();
 
     let files: Vec<ParsedFileDiff> = files_raw
@@ -105,3 +394,112 @@ pub fn parse_diff_parallel(diff_text: &str) -> ParsedDiff {
         total_removed_lines,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "diff --git a/src/a.rs b/src/a.rs\n\
+index 1111111..2222222 100644\n\
+--- a/src/a.rs\n\
++++ b/src/a.rs\n\
+@@ -1,3 +1,4 @@ fn main() {\n\
+ one\n\
+-two\n\
++two!\n\
++three\n\
+ four\n\
+@@ -10,2 +11,2 @@\n\
+ ten\n\
+-eleven\n\
+\\ No newline at end of file\n\
++eleven!\n\
+\\ No newline at end of file\n";
+
+    #[test]
+    fn test_parse_hunks_with_line_numbers() {
+        let parsed = parse_diff_parallel(SAMPLE);
+        assert_eq!(parsed.total_files, 1);
+        assert_eq!(parsed.total_hunks, 2);
+
+        let file = &parsed.files[0];
+        assert_eq!(file.file_path, "src/a.rs");
+        assert!(file.header.starts_with("diff --git a/src/a.rs"));
+
+        let hunk = &file.hunks[0];
+        assert_eq!((hunk.old_start, hunk.old_lines), (1, 3));
+        assert_eq!((hunk.new_start, hunk.new_lines), (1, 4));
+        assert_eq!(hunk.added_lines, 2);
+        assert_eq!(hunk.removed_lines, 1);
+        assert_eq!(hunk.context_lines, 2);
+        assert_eq!(hunk.lines[1].kind, DiffLineKind::Removed);
+        assert_eq!(hunk.lines[1].old_line, Some(2));
+        assert_eq!(hunk.lines[3].content, "three");
+        assert_eq!(hunk.lines[3].new_line, Some(3));
+        assert_eq!(hunk.lines[4].old_line, Some(3));
+        assert_eq!(hunk.lines[4].new_line, Some(4));
+    }
+
+    #[test]
+    fn test_no_newline_marker_is_not_context() {
+        let parsed = parse_diff_parallel(SAMPLE);
+        let hunk = &parsed.files[0].hunks[1];
+        assert_eq!(hunk.context_lines, 1);
+        assert_eq!(hunk.lines.len(), 3);
+        assert!(hunk.lines[1].no_newline);
+        assert!(hunk.lines[2].no_newline);
+    }
+
+    #[test]
+    fn test_file_status_paths_and_binary() {
+        let diff = concat!(
+            "diff --git a/bin.dat b/bin.dat\n",
+            "index bdc955b..8835708 100644\n",
+            "Binary files a/bin.dat and b/bin.dat differ\n",
+            "diff --git a/gone.txt b/gone.txt\n",
+            "deleted file mode 100644\n",
+            "index 286c5f5..0000000\n",
+            "--- a/gone.txt\n",
+            "+++ /dev/null\n",
+            "@@ -1 +0,0 @@\n",
+            "-gone\n",
+            "diff --git a/run.sh b/run.sh\n",
+            "old mode 100644\n",
+            "new mode 100755\n",
+            "diff --git \"a/tab\\t\\303\\251.txt\" \"b/new \\303\\253.txt\"\n",
+            "similarity index 87%\n",
+            "rename from \"tab\\t\\303\\251.txt\"\n",
+            "rename to \"new \\303\\253.txt\"\n",
+            "diff --git a/sp ace.txt b/sp ace.txt\n",
+            "new file mode 100644\n",
+            "index 0000000..422c2b7\n",
+            "--- /dev/null\n",
+            "+++ b/sp ace.txt\t\n",
+            "@@ -0,0 +1 @@\n",
+            "+b\n",
+        );
+        let files = parse_diff_parallel(diff).files;
+        assert_eq!(files.len(), 5);
+
+        assert!(files[0].binary && files[0].hunks.is_empty());
+        assert_eq!(files[0].change, FileChangeKind::Modified);
+
+        assert_eq!(files[1].change, FileChangeKind::Deleted);
+        assert_eq!(files[1].file_path, "gone.txt");
+        assert_eq!(files[1].new_path, None);
+
+        assert_eq!(files[2].change, FileChangeKind::ModeChanged);
+        assert_eq!(files[2].old_mode.as_deref(), Some("100644"));
+        assert_eq!(files[2].new_mode.as_deref(), Some("100755"));
+
+        assert_eq!(files[3].change, FileChangeKind::Renamed);
+        assert_eq!(files[3].old_path.as_deref(), Some("tab\té.txt"));
+        assert_eq!(files[3].file_path, "new ë.txt");
+        assert_eq!(files[3].similarity, Some(87));
+
+        assert_eq!(files[4].change, FileChangeKind::Added);
+        assert_eq!(files[4].file_path, "sp ace.txt");
+        assert_eq!(files[4].old_path, None);
+        assert_eq!(files[4].hunks[0].lines[0].new_line, Some(1));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod conflict_parser;
 pub mod diff_cache;
 pub mod diff_parser;
 pub mod models;
@@ -1,16 +1,73 @@
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// A single line inside a hunk, with its position on each side of the diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// Line number in the old file (None for added lines)
+    pub old_line: Option<u32>,
+    /// Line number in the new file (None for removed lines)
+    pub new_line: Option<u32>,
+    /// Line text without the leading `+`/`-`/` ` marker
+    pub content: String,
+    /// Followed by `\ No newline at end of file`
+    #[serde(default)]
+    pub no_newline: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedHunk {
     pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
     pub added_lines: usize,
     pub removed_lines: usize,
     pub context_lines: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChangeKind {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+    Copied,
+    /// Only the file mode changed (e.g. the executable bit); a mode change
+    /// alongside content changes is `Modified` with both modes set
+    ModeChanged,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedFileDiff {
+    /// New path, or the old path for deletions
     pub file_path: String,
+    /// None for added files
+    pub old_path: Option<String>,
+    /// None for deleted files
+    pub new_path: Option<String>,
+    pub change: FileChangeKind,
+    /// Rename/copy similarity in percent
+    pub similarity: Option<u8>,
+    /// Octal modes ("100644", "100755", "120000" …) when the header has them
+    pub old_mode: Option<String>,
+    pub new_mode: Option<String>,
+    /// "Binary files … differ" or a binary patch; there are no hunks
+    pub binary: bool,
+    /// Raw extended header (`diff --git`, `index`, `---`, `+++` …) used to
+    /// rebuild partial patches for hunk staging.
+    pub header: String,
     pub hunks: Vec<ParsedHunk>,
     pub added_lines: usize,
     pub removed_lines: usize,
@@ -25,6 +82,48 @@ pub struct ParsedDiff {
     pub total_removed_lines: usize,
 }
 
+/// One `<<<<<<<` … `>>>>>>>` region of a conflicted file. Section contents
+/// keep their original line endings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictBlock {
+    pub index: usize,
+    /// 1-based line of the `<<<<<<<` marker
+    pub start_line: usize,
+    /// 1-based line of the `>>>>>>>` marker
+    pub end_line: usize,
+    /// Label after `<<<<<<<`, usually `HEAD`
+    pub ours_label: String,
+    /// Label after `|||||||` (diff3/zdiff3 only)
+    pub base_label: Option<String>,
+    /// Label after `>>>>>>>`, usually the merged branch or commit
+    pub theirs_label: String,
+    pub ours: String,
+    /// Common ancestor content; only present with `merge.conflictStyle=diff3`
+    pub base: Option<String>,
+    pub theirs: String,
+}
+
+/// How to resolve a single conflict block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockChoice {
+    Ours,
+    Theirs,
+    Base,
+    OursThenTheirs,
+    TheirsThenOurs,
+    /// Use `BlockResolution::content`
+    Custom,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockResolution {
+    pub block_index: usize,
+    pub choice: BlockChoice,
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
 /// Represents a parsed diff with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheEntry {
@@ -34,9 +133,10 @@ pub struct CacheEntry {
     pub timestamp: u64,
     pub access_count: u32,
     pub size_bytes: usize,
-    /// File modification time (seconds since epoch) to detect changes
+    /// Object IDs of the diffed content (commit OID for commit diffs); a
+    /// lookup with a different fingerprint is a miss
     #[serde(default)]
-    pub file_mtime: Option<u64>,
+    pub fingerprint: Option<String>,
 }
 
 /// Result of a diff operation
@@ -1,3 +1,5 @@
+use crate::commands::diff::{parse_log_lines, CommitInfo};
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
 use crate::commands::git::git_run;
 use serde::{Deserialize, Serialize};
 
@@ -6,56 +8,154 @@ pub struct BranchInfo {
     pub name: String,
     pub current: bool,
     pub upstream: Option<String>,
+    /// Commits on this branch that the upstream doesn't have
+    pub ahead: u32,
+    /// Commits on the upstream that this branch doesn't have
+    pub behind: u32,
+    /// Upstream is configured but its remote branch was deleted
+    pub upstream_gone: bool,
+    pub commit_hash: String,
+    pub commit_subject: String,
+    /// Committer date, ISO 8601
+    pub commit_date: String,
+}
+
+/// A remote-tracking branch (`refs/remotes/<remote>/<branch>`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteBranchInfo {
+    /// e.g. "origin/main"
+    pub name: String,
+    pub remote: String,
+    /// Branch name on the remote, e.g. "main"
+    pub branch: String,
+    pub commit_hash: String,
+    pub commit_subject: String,
+    pub commit_date: String,
+    /// Local branches whose upstream this is
+    pub tracked_by: Vec<String>,
+}
+
+/// Parse `%(upstream:track)`: "[ahead 1, behind 2]", "[gone]" or "".
+/// Returns `(ahead, behind, gone)`.
+fn parse_track(track: &str) -> (u32, u32, bool) {
+    let inner = track.trim().trim_start_matches('[').trim_end_matches(']');
+    if inner == "gone" {
+        return (0, 0, true);
+    }
+    let mut ahead = 0;
+    let mut behind = 0;
+    for part in inner.split(", ") {
+        match part.split_once(' ') {
+            Some(("ahead", n)) => ahead = n.parse().unwrap_or(0),
+            Some(("behind", n)) => behind = n.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    (ahead, behind, false)
 }
 
 #[tauri::command]
-pub fn get_branches(repo_path: String) -> Result<Vec<BranchInfo>, String> {
+pub fn get_branches(repo_path: String) -> GitResult<Vec<BranchInfo>> {
     let out = git_run(
         &repo_path,
         &[
             "for-each-ref",
             "--sort=refname",
-            "--format=%(HEAD)|%(refname:short)|%(upstream:short)",
+            "--format=%(HEAD)%1f%(refname:short)%1f%(upstream:short)%1f%(upstream:track)%1f\
+%(objectname:short)%1f%(committerdate:iso-strict)%1f%(contents:subject)",
             "refs/heads",
         ],
     )?;
     if !out.success {
-        return Err(out.stderr);
+        return Err(GitError::from_output(&out));
     }
     let branches = out
         .stdout
         .lines()
         .filter(|l| !l.is_empty())
         .map(|line| {
-            let parts: Vec<&str> = line.splitn(3, '|').collect();
-            let current = parts.first().is_some_and(|s| *s == "*");
-            let name = parts.get(1).unwrap_or(&"").to_string();
-            let upstream = parts
-                .get(2)
+            let parts: Vec<&str> = line.splitn(7, '\x1f').collect();
+            let field = |i: usize| parts.get(i).copied().unwrap_or("");
+            let current = field(0) == "*";
+            let upstream = Some(field(2))
                 .filter(|s| !s.is_empty())
                 .map(|s| s.to_string());
+            let (ahead, behind, upstream_gone) = parse_track(field(3));
             BranchInfo {
-                name,
+                name: field(1).to_string(),
                 current,
                 upstream,
+                ahead,
+                behind,
+                upstream_gone,
+                commit_hash: field(4).to_string(),
+                commit_date: field(5).to_string(),
+                commit_subject: field(6).to_string(),
+            }
+        })
+        .collect();
+    Ok(branches)
+}
+
+/// List remote-tracking branches, skipping the `<remote>/HEAD` aliases.
+#[tauri::command]
+pub fn get_remote_branches(repo_path: String) -> GitResult<Vec<RemoteBranchInfo>> {
+    let out = git_run(
+        &repo_path,
+        &[
+            "for-each-ref",
+            "--sort=refname",
+            "--format=%(refname:lstrip=2)%1f%(symref)%1f%(objectname:short)%1f\
+%(committerdate:iso-strict)%1f%(contents:subject)",
+            "refs/remotes",
+        ],
+    )?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    let local = get_branches(repo_path)?;
+
+    let branches = out
+        .stdout
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.splitn(5, '\x1f').collect();
+            let field = |i: usize| parts.get(i).copied().unwrap_or("");
+            if !field(1).is_empty() {
+                return None;
             }
+            let name = field(0).to_string();
+            let (remote, branch) = name.split_once('/')?;
+            Some(RemoteBranchInfo {
+                remote: remote.to_string(),
+                branch: branch.to_string(),
+                commit_hash: field(2).to_string(),
+                commit_date: field(3).to_string(),
+                commit_subject: field(4).to_string(),
+                tracked_by: local
+                    .iter()
+                    .filter(|b| b.upstream.as_deref() == Some(name.as_str()))
+                    .map(|b| b.name.clone())
+                    .collect(),
+                name,
+            })
         })
         .collect();
     Ok(branches)
 }
 
 #[tauri::command]
-pub fn switch_branch(repo_path: String, name: String) -> Result<(), String> {
+pub fn switch_branch(repo_path: String, name: String) -> GitResult<()> {
     let out = git_run(&repo_path, &["switch", &name])?;
     if out.success {
         Ok(())
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
     }
 }
 
 #[tauri::command]
-pub fn create_branch(repo_path: String, name: String, checkout: bool) -> Result<(), String> {
+pub fn create_branch(repo_path: String, name: String, checkout: bool) -> GitResult<()> {
     let out = if checkout {
         git_run(&repo_path, &["switch", "-c", &name])?
     } else {
@@ -64,17 +164,251 @@ pub fn create_branch(repo_path: String, name: String, checkout: bool) -> Result<
     if out.success {
         Ok(())
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
     }
 }
 
 #[tauri::command]
-pub fn delete_branch(repo_path: String, name: String, force: bool) -> Result<(), String> {
+pub fn delete_branch(repo_path: String, name: String, force: bool) -> GitResult<()> {
     let flag = if force { "-D" } else { "-d" };
     let out = git_run(&repo_path, &["branch", flag, &name])?;
     if out.success {
         Ok(())
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
+    }
+}
+
+/// Branch names come from the UI; keep them from being read as options.
+fn check_branch_name(name: &str) -> GitResult<()> {
+    if name.trim().is_empty() || name.starts_with('-') {
+        return Err(format!("Invalid branch name {:?}", name).into());
+    }
+    Ok(())
+}
+
+/// Rename a branch (the current one included). `force` overwrites an
+/// existing branch named `new_name`.
+#[tauri::command]
+pub fn rename_branch(
+    repo_path: String,
+    old_name: String,
+    new_name: String,
+    force: bool,
+) -> GitResult<()> {
+    check_branch_name(&old_name)?;
+    check_branch_name(&new_name)?;
+    let flag = if force { "-M" } else { "-m" };
+    let out = git_run(&repo_path, &["branch", flag, &old_name, &new_name])?;
+    if out.success {
+        Ok(())
+    } else {
+        Err(GitError::from_output(&out))
+    }
+}
+
+/// Set `branch`'s upstream (e.g. "origin/main"), or unset it when
+/// `upstream` is None.
+#[tauri::command]
+pub fn set_branch_upstream(
+    repo_path: String,
+    branch: String,
+    upstream: Option<String>,
+) -> GitResult<()> {
+    check_branch_name(&branch)?;
+    let out = match upstream.filter(|u| !u.trim().is_empty()) {
+        Some(upstream) => git_run(
+            &repo_path,
+            &[
+                "branch",
+                &format!("--set-upstream-to={}", upstream),
+                &branch,
+            ],
+        )?,
+        None => git_run(&repo_path, &["branch", "--unset-upstream", &branch])?,
+    };
+    if out.success {
+        Ok(())
+    } else {
+        Err(GitError::from_output(&out))
+    }
+}
+
+/// Commits listed per side of a comparison; counts are always exact.
+const COMPARE_LIMIT: usize = 500;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BranchComparison {
+    pub base: String,
+    pub head: String,
+    /// None when the branches share no history
+    pub merge_base: Option<String>,
+    /// Commits on `head` that aren't on `base`, newest first
+    pub ahead: Vec<CommitInfo>,
+    /// Commits on `base` that aren't on `head`, newest first
+    pub behind: Vec<CommitInfo>,
+    pub ahead_count: u32,
+    pub behind_count: u32,
+}
+
+fn commits_in_range(repo_path: &str, range: &str) -> GitResult<Vec<CommitInfo>> {
+    let limit = COMPARE_LIMIT.to_string();
+    let out = git_run(
+        repo_path,
+        &[
+            "log",
+            "--format=%H|%h|%an|%ai|%s",
+            "-n",
+            &limit,
+            range,
+            "--",
+        ],
+    )?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    Ok(parse_log_lines(&out.stdout))
+}
+
+/// Commits unique to each of `base` and `head`.
+#[tauri::command]
+pub fn compare_branches(
+    repo_path: String,
+    base: String,
+    head: String,
+) -> GitResult<BranchComparison> {
+    check_branch_name(&base)?;
+    check_branch_name(&head)?;
+    let counts = git_run(
+        &repo_path,
+        &[
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("{}...{}", base, head),
+            "--",
+        ],
+    )?;
+    if !counts.success {
+        return Err(GitError::from_output(&counts));
+    }
+    let mut numbers = counts
+        .stdout
+        .split_whitespace()
+        .map(|n| n.parse::<u32>().unwrap_or(0));
+    let behind_count = numbers.next().unwrap_or(0);
+    let ahead_count = numbers.next().unwrap_or(0);
+
+    let merge_base = git_run(&repo_path, &["merge-base", &base, &head])?;
+    Ok(BranchComparison {
+        ahead: commits_in_range(&repo_path, &format!("{}..{}", base, head))?,
+        behind: commits_in_range(&repo_path, &format!("{}..{}", head, base))?,
+        merge_base: merge_base
+            .success
+            .then(|| merge_base.stdout.trim().to_string()),
+        base,
+        head,
+        ahead_count,
+        behind_count,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CleanupCandidate {
+    #[serde(flatten)]
+    pub branch: BranchInfo,
+    /// Fully merged into the chosen base
+    pub merged: bool,
+}
+
+/// Local branches that look safe to delete: merged into `base`, or whose
+/// upstream is gone. The current branch and `base` itself are never listed.
+#[tauri::command]
+pub fn get_cleanup_candidates(repo_path: String, base: String) -> GitResult<Vec<CleanupCandidate>> {
+    check_branch_name(&base)?;
+    let out = git_run(
+        &repo_path,
+        &[
+            "for-each-ref",
+            "--format=%(refname:short)",
+            &format!("--merged={}", base),
+            "refs/heads",
+        ],
+    )?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    let merged: Vec<&str> = out.stdout.lines().map(str::trim).collect();
+
+    Ok(get_branches(repo_path)?
+        .into_iter()
+        .filter(|b| !b.current && b.name != base)
+        .filter_map(|branch| {
+            let is_merged = merged.contains(&branch.name.as_str());
+            (is_merged || branch.upstream_gone).then_some(CleanupCandidate {
+                branch,
+                merged: is_merged,
+            })
+        })
+        .collect())
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BranchDeleteResult {
+    pub name: String,
+    pub deleted: bool,
+    /// Why the branch wasn't deleted, classified like any command error
+    pub error: Option<GitError>,
+}
+
+/// Delete several branches, continuing past failures. Without `force`,
+/// branches that aren't fully merged are refused individually.
+#[tauri::command]
+pub fn delete_branches(
+    repo_path: String,
+    names: Vec<String>,
+    force: bool,
+) -> GitResult<Vec<BranchDeleteResult>> {
+    let flag = if force { "-D" } else { "-d" };
+    names
+        .into_iter()
+        .map(|name| {
+            let result = check_branch_name(&name).and_then(|_| {
+                let out = git_run(&repo_path, &["branch", flag, &name])?;
+                if out.success {
+                    Ok(())
+                } else {
+                    Err(GitError::from_output(&out))
+                }
+            });
+            // Only a missing git binary aborts the batch
+            match result {
+                Ok(()) => Ok(BranchDeleteResult {
+                    name,
+                    deleted: true,
+                    error: None,
+                }),
+                Err(e) if e.kind == GitErrorKind::GitNotFound => Err(e),
+                Err(e) => Ok(BranchDeleteResult {
+                    name,
+                    deleted: false,
+                    error: Some(e),
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_track() {
+        assert_eq!(parse_track(""), (0, 0, false));
+        assert_eq!(parse_track("[ahead 3]"), (3, 0, false));
+        assert_eq!(parse_track("[behind 2]"), (0, 2, false));
+        assert_eq!(parse_track("[ahead 1, behind 12]"), (1, 12, false));
+        assert_eq!(parse_track("[gone]"), (0, 0, true));
     }
 }
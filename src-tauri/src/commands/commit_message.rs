@@ -0,0 +1,416 @@
+use crate::commands::error::{GitError, GitResult};
+use serde::{Deserialize, Serialize};
+
+/// Types offered by the commit builder.
+const DEFAULT_TYPES: &[&str] = &[
+    "feat", "fix", "chore", "docs", "refactor", "test", "ci", "perf", "style", "revert",
+];
+
+/// Messages git or the app generate itself; never linted.
+const IGNORED_PREFIXES: &[&str] = &["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitFooter {
+    /// e.g. "Refs", "Co-authored-by" or "BREAKING CHANGE"
+    pub token: String,
+    pub value: String,
+}
+
+/// A commit message split into its Conventional Commits parts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConventionalCommit {
+    #[serde(rename = "type")]
+    pub commit_type: String,
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// `!` after the type/scope, or a `BREAKING CHANGE` footer
+    #[serde(default)]
+    pub breaking: bool,
+    pub description: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub footers: Vec<CommitFooter>,
+}
+
+/// Rules applied by `lint_commit_message` and by `commit`/`amend_commit`
+/// when `CommitOptions.lint` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommitLintConfig {
+    /// Allowed types; empty allows any
+    pub types: Vec<String>,
+    /// Allowed scopes; empty allows any
+    pub scopes: Vec<String>,
+    pub require_scope: bool,
+    /// Maximum length of the whole first line, in characters
+    pub max_subject_length: usize,
+}
+
+impl Default for CommitLintConfig {
+    fn default() -> Self {
+        Self {
+            types: DEFAULT_TYPES.iter().map(|t| t.to_string()).collect(),
+            scopes: Vec::new(),
+            require_scope: false,
+            max_subject_length: 72,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    /// First line isn't `type(scope)!: description`
+    HeaderFormat,
+    TypeNotAllowed,
+    ScopeRequired,
+    ScopeNotAllowed,
+    SubjectTooLong,
+    /// Body must be separated from the header by a blank line
+    BodyLeadingBlank,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintIssue {
+    pub rule: LintRule,
+    pub message: String,
+}
+
+fn is_token(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn is_breaking_token(token: &str) -> bool {
+    token == "BREAKING CHANGE" || token == "BREAKING-CHANGE"
+}
+
+/// `token: value` or `token #value`, the two footer forms the spec allows.
+fn parse_footer_line(line: &str) -> Option<CommitFooter> {
+    let (token, value) = match line.split_once(": ") {
+        Some((token, value)) if is_token(token) || is_breaking_token(token) => (token, value),
+        _ => {
+            let (token, value) = line.split_once(" #")?;
+            if !is_token(token) {
+                return None;
+            }
+            (token, value)
+        }
+    };
+    Some(CommitFooter {
+        token: token.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Split `type(scope)!: description`.
+fn parse_header(header: &str) -> Option<(String, Option<String>, bool, String)> {
+    let (prefix, description) = header.split_once(':')?;
+    let description = description.strip_prefix(char::is_whitespace)?.trim();
+    if description.is_empty() {
+        return None;
+    }
+    let (prefix, bang) = match prefix.strip_suffix('!') {
+        Some(p) => (p, true),
+        None => (prefix, false),
+    };
+    let (commit_type, scope) = match prefix.split_once('(') {
+        Some((t, rest)) => {
+            let scope = rest.strip_suffix(')')?;
+            if scope.is_empty() || scope.contains(|c: char| c.is_whitespace() || c == ')') {
+                return None;
+            }
+            (t, Some(scope.to_string()))
+        }
+        None => (prefix, None),
+    };
+    if !is_token(commit_type) {
+        return None;
+    }
+    Some((
+        commit_type.to_string(),
+        scope,
+        bang,
+        description.to_string(),
+    ))
+}
+
+/// Parse `message` as a Conventional Commit. None when the header doesn't
+/// follow `type(scope)!: description`. Only the last paragraph can hold
+/// footers, and only when it opens with a `token: value` line.
+pub fn parse_commit_message(message: &str) -> Option<ConventionalCommit> {
+    let message = message.replace("\r\n", "\n");
+    let mut lines = message.lines();
+    let (commit_type, scope, bang, description) = parse_header(lines.next()?.trim())?;
+    let rest: Vec<&str> = lines.skip_while(|l| l.trim().is_empty()).collect();
+
+    let end = rest.len()
+        - rest
+            .iter()
+            .rev()
+            .take_while(|l| l.trim().is_empty())
+            .count();
+    let last_paragraph = rest[..end]
+        .iter()
+        .rposition(|l| l.trim().is_empty())
+        .map_or(0, |i| i + 1);
+    let footer_start = (last_paragraph < end && parse_footer_line(rest[last_paragraph]).is_some())
+        .then_some(last_paragraph);
+    let (body_lines, footer_lines) = rest.split_at(footer_start.unwrap_or(rest.len()));
+
+    let mut footers: Vec<CommitFooter> = Vec::new();
+    for line in footer_lines {
+        match (parse_footer_line(line), footers.last_mut()) {
+            (Some(footer), _) => footers.push(footer),
+            (None, Some(last)) => {
+                last.value.push('\n');
+                last.value.push_str(line);
+            }
+            (None, None) => {}
+        }
+    }
+    for footer in &mut footers {
+        footer.value = footer.value.trim().to_string();
+    }
+
+    let body = body_lines.join("\n").trim().to_string();
+    let breaking = bang || footers.iter().any(|f| is_breaking_token(&f.token));
+    Some(ConventionalCommit {
+        commit_type,
+        scope,
+        breaking,
+        description,
+        body: (!body.is_empty()).then_some(body),
+        footers,
+    })
+}
+
+/// Check `message` against `config`. Merge, revert and autosquash messages
+/// are always accepted.
+pub fn lint_message(message: &str, config: &CommitLintConfig) -> Vec<LintIssue> {
+    let header = message.lines().next().unwrap_or("").trim_end();
+    if IGNORED_PREFIXES.iter().any(|p| header.starts_with(p)) {
+        return Vec::new();
+    }
+
+    let mut issues = Vec::new();
+    let mut issue = |rule, message: String| issues.push(LintIssue { rule, message });
+
+    let length = header.chars().count();
+    if config.max_subject_length > 0 && length > config.max_subject_length {
+        issue(
+            LintRule::SubjectTooLong,
+            format!(
+                "Subject is {} characters long (max {})",
+                length, config.max_subject_length
+            ),
+        );
+    }
+    if message.lines().nth(1).is_some_and(|l| !l.trim().is_empty()) {
+        issue(
+            LintRule::BodyLeadingBlank,
+            "Separate the body from the subject with a blank line".to_string(),
+        );
+    }
+
+    let Some(parsed) = parse_commit_message(message) else {
+        issue(
+            LintRule::HeaderFormat,
+            "Subject must look like \"type(scope): description\"".to_string(),
+        );
+        return issues;
+    };
+    if !config.types.is_empty() && !config.types.contains(&parsed.commit_type) {
+        issue(
+            LintRule::TypeNotAllowed,
+            format!(
+                "Type \"{}\" is not one of: {}",
+                parsed.commit_type,
+                config.types.join(", ")
+            ),
+        );
+    }
+    match parsed.scope {
+        None if config.require_scope => {
+            issue(LintRule::ScopeRequired, "A scope is required".to_string())
+        }
+        Some(ref scope) if !config.scopes.is_empty() && !config.scopes.contains(scope) => issue(
+            LintRule::ScopeNotAllowed,
+            format!(
+                "Scope \"{}\" is not one of: {}",
+                scope,
+                config.scopes.join(", ")
+            ),
+        ),
+        _ => {}
+    }
+    issues
+}
+
+/// Render `commit` as a message: header, blank line, body, blank line,
+/// footers. A breaking commit without a `BREAKING CHANGE` footer gets `!`.
+pub fn format_message(commit: &ConventionalCommit) -> String {
+    let has_breaking_footer = commit.footers.iter().any(|f| is_breaking_token(&f.token));
+    let mut message = commit.commit_type.trim().to_string();
+    if let Some(scope) = commit
+        .scope
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        message.push_str(&format!("({})", scope));
+    }
+    if commit.breaking && !has_breaking_footer {
+        message.push('!');
+    }
+    message.push_str(": ");
+    message.push_str(commit.description.trim());
+
+    if let Some(body) = commit
+        .body
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+    {
+        message.push_str("\n\n");
+        message.push_str(body);
+    }
+    let footers: Vec<String> = commit
+        .footers
+        .iter()
+        .filter(|f| !f.value.trim().is_empty())
+        .map(|f| format!("{}: {}", f.token.trim(), f.value.trim()))
+        .collect();
+    if !footers.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&footers.join("\n"));
+    }
+    message
+}
+
+/// Fail with a `lint_failed` error listing every issue.
+pub fn ensure_valid_message(message: &str, config: &CommitLintConfig) -> GitResult<()> {
+    let issues = lint_message(message, config);
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(GitError::lint_failed(issues))
+    }
+}
+
+#[tauri::command]
+pub fn parse_conventional_commit(message: String) -> GitResult<Option<ConventionalCommit>> {
+    Ok(parse_commit_message(&message))
+}
+
+/// Lint a message without committing. Uses the default rules when `config`
+/// is omitted.
+#[tauri::command]
+pub fn lint_commit_message(
+    message: String,
+    config: Option<CommitLintConfig>,
+) -> GitResult<Vec<LintIssue>> {
+    Ok(lint_message(&message, &config.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub fn build_commit_message(commit: ConventionalCommit) -> GitResult<String> {
+    if parse_header(&format!("{}: x", commit.commit_type.trim())).is_none() {
+        return Err(format!("Invalid commit type {:?}", commit.commit_type).into());
+    }
+    if commit.description.trim().is_empty() {
+        return Err("Commit description is empty".into());
+    }
+    Ok(format_message(&commit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_format_round_trip() {
+        let message = concat!(
+            "feat(api)!: add paging\n",
+            "\n",
+            "Long explanation\n",
+            "over two lines.\n",
+            "\n",
+            "Refs #42\n",
+            "BREAKING CHANGE: cursor replaces offset\n",
+            "  for all list endpoints",
+        );
+        let parsed = parse_commit_message(message).unwrap();
+        assert_eq!(parsed.commit_type, "feat");
+        assert_eq!(parsed.scope.as_deref(), Some("api"));
+        assert!(parsed.breaking);
+        assert_eq!(parsed.description, "add paging");
+        assert_eq!(
+            parsed.body.as_deref(),
+            Some("Long explanation\nover two lines.")
+        );
+        assert_eq!(parsed.footers.len(), 2);
+        assert_eq!(parsed.footers[0].token, "Refs");
+        assert_eq!(parsed.footers[0].value, "42");
+        assert_eq!(
+            parsed.footers[1].value,
+            "cursor replaces offset\n  for all list endpoints"
+        );
+
+        // A `token: value` line in an earlier paragraph is body text
+        let parsed = parse_commit_message(concat!(
+            "fix: handle timeouts\n",
+            "\n",
+            "Note: retries are capped.\n",
+            "\n",
+            "More detail.\n",
+        ))
+        .unwrap();
+        assert_eq!(
+            parsed.body.as_deref(),
+            Some("Note: retries are capped.\n\nMore detail.")
+        );
+        assert!(parsed.footers.is_empty());
+
+        assert!(parse_commit_message("just a subject").is_none());
+        assert!(parse_commit_message("feat(): empty scope").is_none());
+        assert_eq!(
+            format_message(&parse_commit_message("fix!: drop it").unwrap()),
+            "fix!: drop it"
+        );
+    }
+
+    #[test]
+    fn test_lint_reports_each_rule() {
+        let config = CommitLintConfig {
+            scopes: vec!["ui".to_string()],
+            require_scope: true,
+            max_subject_length: 20,
+            ..Default::default()
+        };
+        let rules = |message: &str| -> Vec<LintRule> {
+            lint_message(message, &config)
+                .into_iter()
+                .map(|i| i.rule)
+                .collect()
+        };
+
+        assert!(rules("fix(ui): ok").is_empty());
+        assert!(rules("Merge branch 'feature/very-long-name'").is_empty());
+        assert_eq!(
+            rules("wip: stuff"),
+            [LintRule::TypeNotAllowed, LintRule::ScopeRequired]
+        );
+        assert_eq!(rules("fix(core): ok"), [LintRule::ScopeNotAllowed]);
+        assert_eq!(
+            rules("fixed things\nbody"),
+            [LintRule::BodyLeadingBlank, LintRule::HeaderFormat]
+        );
+        assert_eq!(
+            rules("fix(ui): a subject that is far too long"),
+            [LintRule::SubjectTooLong]
+        );
+    }
+}
@@ -1,8 +1,28 @@
 use crate::cache::{get_cache, parse_diff_parallel, DiffBatchResult, DiffResult, ParsedDiff};
-use crate::commands::git::git_run;
+use crate::commands::error::{GitError, GitResult};
+use crate::commands::git::{git_run, git_run_with_stdin};
+use crate::commands::history::{parse_numstat_z, CommitFileStat};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 use std::time::Instant;
 
+/// Paths per git invocation when they're passed on the command line.
+const PATHSPEC_CHUNK: usize = 200;
+/// Object ID of the empty tree, the base of a root commit's diff.
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+/// Output options for diffs cached by commit ID. Those entries are shared
+/// between repositories, so nothing configurable (diff.noprefix,
+/// diff.algorithm, textconv and external diff drivers) may change the text.
+const PINNED_DIFF_ARGS: [&str; 6] = [
+    "--no-ext-diff",
+    "--no-textconv",
+    "--no-color",
+    "--src-prefix=a/",
+    "--dst-prefix=b/",
+    "--diff-algorithm=myers",
+];
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CommitInfo {
     pub hash: String,
@@ -21,7 +41,7 @@ pub struct BlameLine {
     pub content: String,
 }
 
-fn parse_log_lines(output: &str) -> Vec<CommitInfo> {
+pub(crate) fn parse_log_lines(output: &str) -> Vec<CommitInfo> {
     output
         .lines()
         .filter_map(|line| {
@@ -40,7 +60,7 @@ fn parse_log_lines(output: &str) -> Vec<CommitInfo> {
         .collect()
 }
 
-async fn parse_diff_async(diff_text: String) -> Result<(ParsedDiff, u64), String> {
+async fn parse_diff_async(diff_text: String) -> GitResult<(ParsedDiff, u64)> {
     let parse_start = Instant::now();
     let parse_input = diff_text.clone();
 
@@ -51,13 +71,169 @@ async fn parse_diff_async(diff_text: String) -> Result<(ParsedDiff, u64), String
     Ok((parsed, parse_start.elapsed().as_millis() as u64))
 }
 
+/// Run `git <args> -- <paths>` in chunks, concatenating stdout. Failed
+/// chunks (e.g. `ls-tree HEAD` before the first commit) contribute nothing.
+fn run_with_paths(repo_path: &str, args: &[&str], paths: &[String]) -> GitResult<String> {
+    let mut stdout = String::new();
+    for chunk in paths.chunks(PATHSPEC_CHUNK) {
+        let mut full: Vec<&str> = vec!["--literal-pathspecs"];
+        full.extend_from_slice(args);
+        full.push("--");
+        full.extend(chunk.iter().map(String::as_str));
+        let out = git_run(repo_path, &full)?;
+        if out.success {
+            stdout.push_str(&out.stdout);
+        }
+    }
+    Ok(stdout)
+}
+
+/// `<meta>\t<path>` records from `ls-files -s -z` / `ls-tree -z`, grouped
+/// by path. Conflicted paths have one record per stage.
+fn parse_entries(stdout: &str) -> HashMap<String, Vec<String>> {
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for record in stdout.split('\0') {
+        if let Some((meta, path)) = record.split_once('\t') {
+            map.entry(path.to_string())
+                .or_default()
+                .push(meta.to_string());
+        }
+    }
+    map
+}
+
+/// Working-tree side of an unstaged diff.
+enum WorktreeState {
+    /// `-` for a missing file, or the target of a symlink
+    Known(String),
+    /// Regular file to hash; "x" when executable, "f" otherwise
+    Hash(&'static str),
+}
+
+/// None for directories such as submodules and for unreadable paths.
+fn worktree_state(repo_path: &str, file_path: &str) -> Option<WorktreeState> {
+    let full = Path::new(repo_path).join(file_path);
+    let meta = match std::fs::symlink_metadata(&full) {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Some(WorktreeState::Known("-".to_string()))
+        }
+        Err(_) => return None,
+    };
+    if meta.file_type().is_symlink() {
+        let target = std::fs::read_link(&full).ok()?;
+        return Some(WorktreeState::Known(format!(
+            "link:{}",
+            target.to_string_lossy()
+        )));
+    }
+    if !meta.is_file() {
+        return None;
+    }
+    #[cfg(unix)]
+    let exec = {
+        use std::os::unix::fs::PermissionsExt;
+        meta.permissions().mode() & 0o111 != 0
+    };
+    #[cfg(not(unix))]
+    let exec = false;
+    Some(WorktreeState::Hash(if exec { "x" } else { "f" }))
+}
+
+/// Identify the content each file's diff is computed from: the HEAD and
+/// index entries for a staged diff, the index entries and working-tree blob
+/// for an unstaged one. Equal fingerprints mean byte-identical diffs, so a
+/// cache hit on the fingerprint is always correct. Files whose content
+/// can't be pinned down (submodules, unreadable files, and regular files
+/// whose name holds a line break `hash-object --stdin-paths` can't take)
+/// are left out and shouldn't be cached.
+pub(crate) fn diff_fingerprints(
+    repo_path: &str,
+    files: &[String],
+    staged: bool,
+) -> HashMap<String, String> {
+    let mut fingerprints = HashMap::new();
+    if files.is_empty() {
+        return fingerprints;
+    }
+    let Ok(index) = run_with_paths(repo_path, &["ls-files", "-s", "-z"], files) else {
+        return fingerprints;
+    };
+    let index = parse_entries(&index);
+    let index_of = |file: &String| {
+        index
+            .get(file)
+            .map(|entries| entries.join(","))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    if staged {
+        let Ok(head) = run_with_paths(repo_path, &["ls-tree", "-z", "HEAD"], files) else {
+            return fingerprints;
+        };
+        let head = parse_entries(&head);
+        for file in files {
+            let head_entry = head
+                .get(file)
+                .map(|entries| entries.join(","))
+                .unwrap_or_else(|| "-".to_string());
+            fingerprints.insert(file.clone(), format!("{} {}", head_entry, index_of(file)));
+        }
+        return fingerprints;
+    }
+
+    // Regular files are hashed by git so clean filters and eol conversion
+    // apply exactly as they do for the diff itself
+    let mut to_hash: Vec<(&String, &str)> = Vec::new();
+    for file in files {
+        match worktree_state(repo_path, file) {
+            Some(WorktreeState::Known(state)) => {
+                fingerprints.insert(file.clone(), format!("{} {}", index_of(file), state));
+            }
+            // One path per line: a name with a line break would shift
+            // every following hash onto the wrong file
+            Some(WorktreeState::Hash(_)) if file.contains(['\n', '\r']) => {}
+            Some(WorktreeState::Hash(mode)) => to_hash.push((file, mode)),
+            None => {}
+        }
+    }
+    if to_hash.is_empty() {
+        return fingerprints;
+    }
+    let input: String = to_hash.iter().map(|(f, _)| format!("{}\n", f)).collect();
+    let Ok(out) = git_run_with_stdin(repo_path, &["hash-object", "--stdin-paths"], &input) else {
+        return fingerprints;
+    };
+    let oids: Vec<&str> = out.stdout.lines().collect();
+    if !out.success || oids.len() != to_hash.len() {
+        return fingerprints;
+    }
+    for ((file, mode), oid) in to_hash.into_iter().zip(oids) {
+        fingerprints.insert(
+            file.clone(),
+            format!("{} {}:{}", index_of(file), mode, oid.trim()),
+        );
+    }
+    fingerprints
+}
+
+/// Fingerprints of `files` that still match `before`, i.e. whose content
+/// didn't change while their diffs were being computed. Only those diffs
+/// are safe to cache.
+fn unchanged_fingerprints(
+    repo_path: &str,
+    files: &[String],
+    staged: bool,
+    before: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut after = diff_fingerprints(repo_path, files, staged);
+    after.retain(|file, fp| before.get(file) == Some(fp));
+    after
+}
+
 /// Returns the unified diff for a file (staged or unstaged).
 #[tauri::command]
-pub async fn get_diff(
-    repo_path: String,
-    file_path: String,
-    staged: bool,
-) -> Result<String, String> {
+pub async fn get_diff(repo_path: String, file_path: String, staged: bool) -> GitResult<String> {
     let file_ref = file_path.as_str();
     let args: Vec<&str> = if staged {
         vec!["diff", "--staged", "--", file_ref]
@@ -91,34 +267,252 @@ pub async fn get_diff(
     })
 }
 
-/// Full unified patch for a single commit (all changed files).
+/// Full unified patch for a single commit (all changed files). Cached by
+/// commit ID, since a commit's diff never changes.
 #[tauri::command]
-pub async fn get_commit_diff(repo_path: String, hash: String) -> Result<String, String> {
-    let hash_ref = hash.as_str();
-    // --root handles the initial commit (no parent)
-    let out = git_run(
+pub async fn get_commit_diff(repo_path: String, hash: String) -> GitResult<String> {
+    let cache = get_cache();
+    let commit_oid = git_run(
         &repo_path,
         &[
-            "diff-tree",
-            "--root",
-            "--no-commit-id",
-            "-p",
-            "-r",
-            hash_ref,
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", hash),
         ],
     )?;
+    let commit_oid = commit_oid
+        .success
+        .then(|| commit_oid.stdout.trim().to_string());
+    if let Some(cached) = commit_oid
+        .as_deref()
+        .and_then(|oid| cache.get_commit(oid, ""))
+    {
+        return Ok(cached.diff_text);
+    }
+
+    let hash_ref = hash.as_str();
+    // --root handles the initial commit (no parent)
+    let mut args = vec!["diff-tree", "--root", "--no-commit-id", "-p", "-r"];
+    args.extend(PINNED_DIFF_ARGS);
+    args.push(hash_ref);
+    let out = git_run(&repo_path, &args)?;
     if out.success {
+        if let Some(ref oid) = commit_oid {
+            cache.set_commit(oid, "", out.stdout.clone(), None);
+        }
         return Ok(out.stdout);
     }
     // Fallback: diff against the empty tree SHA
-    let empty_tree = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
-    let out2 = git_run(&repo_path, &["diff", empty_tree, hash_ref])?;
+    let out2 = git_run(&repo_path, &["diff", EMPTY_TREE, hash_ref])?;
     Ok(out2.stdout)
 }
 
+/// Both ends of a commit diff, as full object IDs.
+struct DiffRange {
+    /// First parent, an explicit base, or the empty tree for root commits
+    base: String,
+    target: String,
+}
+
+impl DiffRange {
+    /// Cache namespace for the range's diffs
+    fn key(&self) -> String {
+        format!("{}..{}", self.base, self.target)
+    }
+}
+
+fn resolve_commit(repo_path: &str, rev: &str) -> GitResult<String> {
+    if rev.starts_with('-') {
+        return Err(format!("Invalid revision {:?}", rev).into());
+    }
+    let out = git_run(
+        repo_path,
+        &["rev-parse", "--verify", &format!("{}^{{commit}}", rev)],
+    )?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    Ok(out.stdout.trim().to_string())
+}
+
+fn resolve_range(repo_path: &str, target: &str, base: Option<&str>) -> GitResult<DiffRange> {
+    let target = resolve_commit(repo_path, target)?;
+    let base = match base {
+        Some(base) => resolve_commit(repo_path, base)?,
+        None => {
+            let parent = git_run(
+                repo_path,
+                &["rev-parse", "--verify", "--quiet", &format!("{}^1", target)],
+            )?;
+            if parent.success {
+                parent.stdout.trim().to_string()
+            } else {
+                EMPTY_TREE.to_string()
+            }
+        }
+    };
+    Ok(DiffRange { base, target })
+}
+
+/// File list of a commit diff, without any patch text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitDiffSummary {
+    /// Resolved base (the empty tree for root commits)
+    pub base: String,
+    pub target: String,
+    pub files: Vec<CommitFileStat>,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+/// Files changed between `base` and `target` (commits, branches, tags …)
+/// with line counts. Without `base` the target commit is compared with its
+/// first parent. Load the patches with `get_commit_file_diffs` as the user
+/// scrolls instead of fetching one giant diff.
+#[tauri::command]
+pub async fn get_commit_diff_summary(
+    repo_path: String,
+    target: String,
+    base: Option<String>,
+) -> GitResult<CommitDiffSummary> {
+    let range = resolve_range(&repo_path, &target, base.as_deref())?;
+    let out = git_run(
+        &repo_path,
+        &["diff", "-M", "--numstat", "-z", &range.base, &range.target],
+    )?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    let files = parse_numstat_z(&out.stdout);
+    Ok(CommitDiffSummary {
+        additions: files.iter().filter_map(|f| f.additions).sum(),
+        deletions: files.iter().filter_map(|f| f.deletions).sum(),
+        base: range.base,
+        target: range.target,
+        files,
+    })
+}
+
+/// A file `get_commit_file_diffs` couldn't load.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDiffError {
+    pub file_path: String,
+    pub error: GitError,
+}
+
+/// Loaded diffs plus the files that failed, so one bad file doesn't hide
+/// the rest.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitFileDiffs {
+    #[serde(flatten)]
+    pub batch: DiffBatchResult,
+    pub errors: Vec<FileDiffError>,
+}
+
+/// Cache name of a file's diff. A rename is only detected when both paths
+/// are in the pathspec, so the old path is part of what was asked for.
+fn commit_file_key(file: &CommitFileStat) -> String {
+    match &file.old_path {
+        // NUL can't appear in a git path
+        Some(old_path) => format!("{}\0{}", old_path, file.path),
+        None => file.path.clone(),
+    }
+}
+
+/// Parsed per-file diffs between `base` and `target` for just `files`,
+/// passed as listed in `CommitDiffSummary::files` so renames keep their old
+/// path (line counts are ignored). Results are cached permanently: the
+/// range is pinned to object IDs, so it can't change.
+#[tauri::command]
+pub async fn get_commit_file_diffs(
+    repo_path: String,
+    target: String,
+    base: Option<String>,
+    files: Vec<CommitFileStat>,
+) -> GitResult<CommitFileDiffs> {
+    let cache = get_cache();
+    let range = resolve_range(&repo_path, &target, base.as_deref())?;
+    let range_key = range.key();
+    let mut diffs = Vec::new();
+    let mut errors = Vec::new();
+    let mut misses = Vec::new();
+
+    for file in files {
+        match cache.get_commit(&range_key, &commit_file_key(&file)) {
+            Some(cached) => diffs.push(DiffResult {
+                file_path: file.path,
+                diff_text: cached.diff_text,
+                parsed: cached.parsed,
+                parse_time_ms: 0,
+                from_cache: true,
+            }),
+            None => misses.push(file),
+        }
+    }
+    let cache_hits = diffs.len();
+
+    let mut tasks = Vec::new();
+    for file in misses {
+        let repo_path = repo_path.clone();
+        let (base, target) = (range.base.clone(), range.target.clone());
+        let file_path = file.path.clone();
+        let task = tokio::spawn(async move {
+            let mut args = vec!["--literal-pathspecs", "diff", "-M"];
+            args.extend(PINNED_DIFF_ARGS);
+            args.extend([base.as_str(), &target, "--"]);
+            args.extend(file.old_path.as_deref());
+            args.push(&file.path);
+            let out = git_run(&repo_path, &args)?;
+            if !out.success {
+                return Err(GitError::from_output(&out));
+            }
+            let (parsed, parse_time_ms) = parse_diff_async(out.stdout.clone()).await?;
+            Ok::<_, GitError>((file, out.stdout, parsed, parse_time_ms))
+        });
+        tasks.push((file_path, task));
+    }
+    for (file_path, task) in tasks {
+        let (file, diff_text, parsed, parse_time_ms) = match task.await {
+            Ok(Ok(result)) => result,
+            Ok(Err(error)) => {
+                errors.push(FileDiffError { file_path, error });
+                continue;
+            }
+            Err(e) => {
+                let error = format!("Commit diff task join error: {}", e).into();
+                errors.push(FileDiffError { file_path, error });
+                continue;
+            }
+        };
+        cache.set_commit(
+            &range_key,
+            &commit_file_key(&file),
+            diff_text.clone(),
+            Some(parsed.clone()),
+        );
+        diffs.push(DiffResult {
+            file_path: file.path,
+            diff_text,
+            parsed: Some(parsed),
+            parse_time_ms,
+            from_cache: false,
+        });
+    }
+
+    Ok(CommitFileDiffs {
+        batch: DiffBatchResult {
+            total_from_compute: diffs.len() - cache_hits,
+            total_cache_hits: cache_hits,
+            diffs,
+        },
+        errors,
+    })
+}
+
 /// Commit log for the whole repo (recent commits).
 #[tauri::command]
-pub async fn get_log(repo_path: String, limit: usize) -> Result<Vec<CommitInfo>, String> {
+pub async fn get_log(repo_path: String, limit: usize) -> GitResult<Vec<CommitInfo>> {
     let limit_str = limit.to_string();
     let out = git_run(
         &repo_path,
@@ -136,14 +530,14 @@ pub async fn get_log(repo_path: String, limit: usize) -> Result<Vec<CommitInfo>,
         {
             return Ok(vec![]);
         }
-        return Err(out.stderr.trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(parse_log_lines(&out.stdout))
 }
 
 /// Commit log for a specific file.
 #[tauri::command]
-pub async fn get_file_log(repo_path: String, file_path: String) -> Result<Vec<CommitInfo>, String> {
+pub async fn get_file_log(repo_path: String, file_path: String) -> GitResult<Vec<CommitInfo>> {
     let file_ref = file_path.as_str();
     let out = git_run(
         &repo_path,
@@ -162,18 +556,18 @@ pub async fn get_file_log(repo_path: String, file_path: String) -> Result<Vec<Co
         {
             return Ok(vec![]);
         }
-        return Err(out.stderr.trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(parse_log_lines(&out.stdout))
 }
 
 /// Blame a file — returns one entry per line using git's porcelain blame format.
 #[tauri::command]
-pub async fn get_blame(repo_path: String, file_path: String) -> Result<Vec<BlameLine>, String> {
+pub async fn get_blame(repo_path: String, file_path: String) -> GitResult<Vec<BlameLine>> {
     let file_ref = file_path.as_str();
     let out = git_run(&repo_path, &["blame", "--porcelain", file_ref])?;
     if !out.success {
-        return Err(out.stderr.trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(parse_blame_porcelain(&out.stdout))
 }
@@ -247,11 +641,11 @@ fn epoch_to_date(epoch: i64) -> String {
 
 /// Returns the full content of a file at HEAD (for the "Full file" tab).
 #[tauri::command]
-pub async fn get_file_content(repo_path: String, file_path: String) -> Result<String, String> {
+pub async fn get_file_content(repo_path: String, file_path: String) -> GitResult<String> {
     let file_ref = file_path.as_str();
     let out = git_run(&repo_path, &["show", &format!("HEAD:{}", file_ref)])?;
     if !out.success {
-        return Err(out.stderr.trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(out.stdout)
 }
@@ -267,11 +661,14 @@ pub async fn get_diff_cached(
     repo_path: String,
     file_path: String,
     staged: bool,
-) -> Result<DiffResult, String> {
+) -> GitResult<DiffResult> {
     let cache = get_cache();
+    let files = [file_path.clone()];
+    let fingerprints = diff_fingerprints(&repo_path, &files, staged);
+    let fingerprint = fingerprints.get(&file_path);
 
     // Check cache first
-    if let Some(cached) = cache.get(&repo_path, &file_path, staged) {
+    if let Some(cached) = fingerprint.and_then(|fp| cache.get(&repo_path, &file_path, staged, fp)) {
         cache.log_access(true);
         let mut parse_time_ms = 0u64;
         let mut parsed = cached.parsed.clone();
@@ -280,13 +677,16 @@ pub async fn get_diff_cached(
             let (computed, parse_ms) = parse_diff_async(cached.diff_text.clone()).await?;
             parse_time_ms = parse_ms;
             parsed = Some(computed.clone());
-            cache.set_with_parsed(
-                &repo_path,
-                &file_path,
-                staged,
-                cached.diff_text.clone(),
-                Some(computed),
-            );
+            if let Some(fp) = fingerprint {
+                cache.set_with_parsed(
+                    &repo_path,
+                    &file_path,
+                    staged,
+                    fp,
+                    cached.diff_text.clone(),
+                    Some(computed),
+                );
+            }
         }
 
         return Ok(DiffResult {
@@ -304,14 +704,19 @@ pub async fn get_diff_cached(
     let diff_text = get_diff(repo_path.clone(), file_path.clone(), staged).await?;
     let (parsed, parse_time_ms) = parse_diff_async(diff_text.clone()).await?;
 
-    // Store in cache for future access
-    cache.set_with_parsed(
-        &repo_path,
-        &file_path,
-        staged,
-        diff_text.clone(),
-        Some(parsed.clone()),
-    );
+    // Store in cache for future access, unless the file changed meanwhile
+    if let Some(fp) =
+        unchanged_fingerprints(&repo_path, &files, staged, &fingerprints).get(&file_path)
+    {
+        cache.set_with_parsed(
+            &repo_path,
+            &file_path,
+            staged,
+            fp,
+            diff_text.clone(),
+            Some(parsed.clone()),
+        );
+    }
 
     Ok(DiffResult {
         file_path,
@@ -331,16 +736,20 @@ pub async fn get_diff_batch(
     repo_path: String,
     files: Vec<String>,
     staged: bool,
-) -> Result<DiffBatchResult, String> {
+) -> GitResult<DiffBatchResult> {
     let cache = get_cache();
     let mut diffs = Vec::new();
     let mut cache_hits = 0;
     let mut cache_misses = 0;
     let mut miss_tasks = Vec::new();
+    let fingerprints = diff_fingerprints(&repo_path, &files, staged);
 
     for file_path in files {
+        let fingerprint = fingerprints.get(&file_path);
         // Try cache first
-        if let Some(cached) = cache.get(&repo_path, &file_path, staged) {
+        if let Some(cached) =
+            fingerprint.and_then(|fp| cache.get(&repo_path, &file_path, staged, fp))
+        {
             cache_hits += 1;
             let mut parse_time_ms = 0u64;
             let mut parsed = cached.parsed.clone();
@@ -349,13 +758,16 @@ pub async fn get_diff_batch(
                 let (computed, parse_ms) = parse_diff_async(cached.diff_text.clone()).await?;
                 parse_time_ms = parse_ms;
                 parsed = Some(computed.clone());
-                cache.set_with_parsed(
-                    &repo_path,
-                    &file_path,
-                    staged,
-                    cached.diff_text.clone(),
-                    Some(computed),
-                );
+                if let Some(fp) = fingerprint {
+                    cache.set_with_parsed(
+                        &repo_path,
+                        &file_path,
+                        staged,
+                        fp,
+                        cached.diff_text.clone(),
+                        Some(computed),
+                    );
+                }
             }
 
             diffs.push(DiffResult {
@@ -374,7 +786,7 @@ pub async fn get_diff_batch(
         miss_tasks.push(tokio::spawn(async move {
             let diff_text = get_diff(repo_clone.clone(), file_clone.clone(), staged).await?;
             let (parsed, parse_time_ms) = parse_diff_async(diff_text.clone()).await?;
-            Ok::<(String, String, ParsedDiff, u64), String>((
+            Ok::<(String, String, ParsedDiff, u64), GitError>((
                 file_clone,
                 diff_text,
                 parsed,
@@ -383,25 +795,10 @@ pub async fn get_diff_batch(
         }));
     }
 
+    let mut computed = Vec::new();
     for task in miss_tasks {
         match task.await {
-            Ok(Ok((file_path, diff_text, parsed, parse_time_ms))) => {
-                cache.set_with_parsed(
-                    &repo_path,
-                    &file_path,
-                    staged,
-                    diff_text.clone(),
-                    Some(parsed.clone()),
-                );
-                cache_misses += 1;
-                diffs.push(DiffResult {
-                    file_path,
-                    diff_text,
-                    parsed: Some(parsed),
-                    parse_time_ms,
-                    from_cache: false,
-                });
-            }
+            Ok(Ok(result)) => computed.push(result),
             Ok(Err(e)) => {
                 eprintln!("Failed to compute diff in batch: {}", e);
             }
@@ -411,6 +808,29 @@ pub async fn get_diff_batch(
         }
     }
 
+    let computed_files: Vec<String> = computed.iter().map(|(f, ..)| f.clone()).collect();
+    let stable = unchanged_fingerprints(&repo_path, &computed_files, staged, &fingerprints);
+    for (file_path, diff_text, parsed, parse_time_ms) in computed {
+        if let Some(fp) = stable.get(&file_path) {
+            cache.set_with_parsed(
+                &repo_path,
+                &file_path,
+                staged,
+                fp,
+                diff_text.clone(),
+                Some(parsed.clone()),
+            );
+        }
+        cache_misses += 1;
+        diffs.push(DiffResult {
+            file_path,
+            diff_text,
+            parsed: Some(parsed),
+            parse_time_ms,
+            from_cache: false,
+        });
+    }
+
     let stats = cache.stats();
     println!(
         "📦 Batch diff: {} files | Cache: {}/{}✓ | HitRate: {:.1}% | Memory: {}KB | Disk: {}KB",
@@ -438,7 +858,7 @@ pub async fn preload_visible_diffs(
     repo_path: String,
     files: Vec<String>,
     staged: bool,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let repo_path_clone = repo_path.clone();
     let files_clone = files.clone(); // Clone before move
 
@@ -448,10 +868,19 @@ pub async fn preload_visible_diffs(
         let start = std::time::Instant::now();
         let mut loaded = 0;
         let mut skipped = 0;
+        let fingerprints = diff_fingerprints(&repo_path_clone, &files_clone, staged);
 
         for file_path in &files_clone {
+            // Diffs that can't be fingerprinted aren't cached at all
+            let Some(fingerprint) = fingerprints.get(file_path) else {
+                skipped += 1;
+                continue;
+            };
             // Skip if already cached
-            if cache.get(&repo_path_clone, file_path, staged).is_some() {
+            if cache
+                .get(&repo_path_clone, file_path, staged, fingerprint)
+                .is_some()
+            {
                 skipped += 1;
                 continue;
             }
@@ -459,19 +888,30 @@ pub async fn preload_visible_diffs(
             // Load and cache
             match get_diff(repo_path_clone.clone(), file_path.clone(), staged).await {
                 Ok(diff_text) => {
+                    let stable = unchanged_fingerprints(
+                        &repo_path_clone,
+                        std::slice::from_ref(file_path),
+                        staged,
+                        &fingerprints,
+                    );
+                    let Some(fingerprint) = stable.get(file_path) else {
+                        skipped += 1;
+                        continue;
+                    };
                     match parse_diff_async(diff_text.clone()).await {
                         Ok((parsed, _)) => {
                             cache.set_with_parsed(
                                 &repo_path_clone,
                                 file_path,
                                 staged,
+                                fingerprint,
                                 diff_text,
                                 Some(parsed),
                             );
                         }
                         Err(e) => {
                             eprintln!("Failed to parse preloaded diff for {}: {}", file_path, e);
-                            cache.set(&repo_path_clone, file_path, staged, diff_text);
+                            cache.set(&repo_path_clone, file_path, staged, fingerprint, diff_text);
                         }
                     }
                     loaded += 1;
@@ -500,7 +940,7 @@ pub async fn preload_visible_diffs(
 
 /// Clear cache for a repository (Phase 1 utility)
 #[tauri::command]
-pub async fn clear_diff_cache(repo_path: String) -> Result<String, String> {
+pub async fn clear_diff_cache(repo_path: String) -> GitResult<String> {
     let cache = get_cache();
     cache.clear_repo(&repo_path);
     println!("🗑️ Cache cleared for {}", repo_path);
@@ -513,7 +953,7 @@ pub async fn invalidate_diff(
     repo_path: String,
     file_path: String,
     staged: bool,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let cache = get_cache();
     cache.invalidate(&repo_path, &file_path, staged);
     Ok(format!("Invalidated {}", file_path))
@@ -521,7 +961,7 @@ pub async fn invalidate_diff(
 
 /// Get cache statistics (Phase 1 diagnostics)
 #[tauri::command]
-pub async fn get_cache_stats() -> Result<serde_json::Value, String> {
+pub async fn get_cache_stats() -> GitResult<serde_json::Value> {
     let cache = get_cache();
     let stats = cache.stats();
 
@@ -542,3 +982,24 @@ pub async fn get_cache_stats() -> Result<serde_json::Value, String> {
         "warm_loaded": stats.warm_loaded,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entries_groups_stages() {
+        let out = concat!(
+            "100644 aaa 1\tboth.txt\0",
+            "100644 bbb 2\tboth.txt\0",
+            "100644 ccc 3\tboth.txt\0",
+            "100755 ddd 0\tdir/run me.sh\0",
+        );
+        let entries = parse_entries(out);
+        assert_eq!(
+            entries["both.txt"],
+            ["100644 aaa 1", "100644 bbb 2", "100644 ccc 3"]
+        );
+        assert_eq!(entries["dir/run me.sh"], ["100755 ddd 0"]);
+    }
+}
@@ -0,0 +1,336 @@
+use crate::commands::commit_message::LintIssue;
+use crate::commands::git::GitOutput;
+use crate::commands::hooks::HookFailure;
+use serde::Serialize;
+
+/// Broad failure categories the frontend can switch on to offer targeted
+/// recovery actions instead of dumping stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitErrorKind {
+    /// git binary missing or could not be spawned
+    GitNotFound,
+    NotARepository,
+    AuthenticationFailed,
+    /// Push rejected because the remote has commits we don't (non-fast-forward)
+    PushRejected,
+    MergeConflict,
+    /// Local changes would be overwritten by checkout/merge/pull
+    UncommittedChanges,
+    NothingToCommit,
+    /// Repository has no commits yet
+    EmptyRepository,
+    /// Current branch has no upstream configured
+    NoUpstream,
+    /// Remote unreachable, DNS failure, timeout …
+    Network,
+    /// Branch, tag, remote or revision doesn't exist
+    NotFound,
+    /// Branch, tag, remote or path already exists
+    AlreadyExists,
+    /// Another git process holds `index.lock`
+    Locked,
+    /// A client-side hook (pre-commit, commit-msg, pre-push …) rejected the operation
+    HookFailed,
+    /// Commit message broke the conventional-commit rules; nothing was run
+    LintFailed,
+    /// GPG/SSH commit or tag signing failed (missing key, gpg not installed …)
+    SigningFailed,
+    /// Stopped by the user via `cancel_operation`
+    Cancelled,
+    /// GitHub REST API error
+    #[serde(rename = "github")]
+    GitHub,
+    Io,
+    Other,
+}
+
+/// Error returned by every Tauri command. Serialized to the frontend as
+/// `{ kind, message, stderr, hook, lint }`.
+#[derive(Debug, Clone, Serialize)]
+pub struct GitError {
+    pub kind: GitErrorKind,
+    /// Human-readable summary, safe to show in a toast
+    pub message: String,
+    /// Raw stderr from git (empty for non-git failures)
+    pub stderr: String,
+    /// Set for `hook_failed` errors
+    pub hook: Option<Box<HookFailure>>,
+    /// Set for `lint_failed` errors
+    pub lint: Option<Vec<LintIssue>>,
+}
+
+pub type GitResult<T> = Result<T, GitError>;
+
+impl GitError {
+    pub fn new(kind: GitErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            stderr: String::new(),
+            hook: None,
+            lint: None,
+        }
+    }
+
+    pub fn hook_failed(failure: HookFailure) -> Self {
+        Self {
+            kind: GitErrorKind::HookFailed,
+            message: format!(
+                "{} hook failed (exit code {})",
+                failure.hook, failure.exit_code
+            ),
+            stderr: failure.output.clone(),
+            hook: Some(Box::new(failure)),
+            lint: None,
+        }
+    }
+
+    pub fn lint_failed(issues: Vec<LintIssue>) -> Self {
+        let message = match issues.as_slice() {
+            [issue] => issue.message.clone(),
+            _ => format!("Commit message has {} problems", issues.len()),
+        };
+        Self {
+            kind: GitErrorKind::LintFailed,
+            message,
+            stderr: String::new(),
+            hook: None,
+            lint: Some(issues),
+        }
+    }
+
+    /// Build an error from a failed git invocation, classifying its stderr.
+    pub fn from_output(out: &GitOutput) -> Self {
+        // Some commands (commit, stash, merge) report failures on stdout.
+        let raw = format!("{}\n{}", out.stdout.trim(), out.stderr.trim());
+        Self::from_stderr(&raw)
+    }
+
+    pub fn from_stderr(stderr: &str) -> Self {
+        let stderr = stderr.trim();
+        let message = if stderr.is_empty() {
+            "git command failed".to_string()
+        } else {
+            summarize(stderr)
+        };
+        Self {
+            kind: classify(stderr),
+            message,
+            stderr: stderr.to_string(),
+            hook: None,
+            lint: None,
+        }
+    }
+}
+
+impl std::fmt::Display for GitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for GitError {}
+
+impl From<String> for GitError {
+    fn from(message: String) -> Self {
+        Self::new(GitErrorKind::Other, message)
+    }
+}
+
+impl From<&str> for GitError {
+    fn from(message: &str) -> Self {
+        Self::new(GitErrorKind::Other, message)
+    }
+}
+
+impl From<std::io::Error> for GitError {
+    fn from(e: std::io::Error) -> Self {
+        Self::new(GitErrorKind::Io, e.to_string())
+    }
+}
+
+impl From<reqwest::Error> for GitError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::new(GitErrorKind::Network, e.to_string())
+    }
+}
+
+/// Strip git's `fatal:`/`error:` prefixes and `hint:` noise so the message
+/// reads well in the UI. The untouched text stays in `stderr`.
+fn summarize(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("hint:"))
+        .map(|l| {
+            l.strip_prefix("fatal: ")
+                .or_else(|| l.strip_prefix("error: "))
+                .unwrap_or(l)
+        })
+        .collect();
+    if lines.is_empty() {
+        stderr.to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// Map git stderr onto a [`GitErrorKind`]. Order matters: more specific
+/// patterns are checked before generic ones.
+pub fn classify(stderr: &str) -> GitErrorKind {
+    // Hints describe other situations ("a branch that already exists …")
+    let s = stderr
+        .lines()
+        .filter(|l| !l.trim_start().starts_with("hint:"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+    let has = |needle: &str| s.contains(needle);
+
+    if has("not a git repository") {
+        GitErrorKind::NotARepository
+    } else if has("failed to sign")
+        || has("cannot run gpg")
+        || has("cannot run ssh-keygen")
+        || has("no secret key")
+        || has("couldn't load public key")
+        || has("gpg: signing failed")
+    {
+        GitErrorKind::SigningFailed
+    } else if has("authentication failed")
+        || has("permission denied (publickey")
+        || has("could not read username")
+        || has("could not read password")
+        || has("invalid username or password")
+        || has("403 forbidden")
+        || has("the requested url returned error: 403")
+        || has("the requested url returned error: 401")
+    {
+        GitErrorKind::AuthenticationFailed
+    } else if has("non-fast-forward")
+        || has("[rejected]")
+        || has("fetch first")
+        || has("updates were rejected")
+    {
+        GitErrorKind::PushRejected
+    } else if has("conflict (")
+        || has("automatic merge failed")
+        || has("fix conflicts")
+        || has("unmerged files")
+        || has("you have unmerged paths")
+        || has("could not apply")
+    {
+        GitErrorKind::MergeConflict
+    } else if has("would be overwritten")
+        || has("please commit your changes or stash them")
+        || has("your local changes")
+    {
+        GitErrorKind::UncommittedChanges
+    } else if has("nothing to commit") || has("no changes added to commit") {
+        GitErrorKind::NothingToCommit
+    } else if has("does not have any commits") || has("bad default revision") {
+        GitErrorKind::EmptyRepository
+    } else if has("has no upstream branch") || has("no upstream configured") {
+        GitErrorKind::NoUpstream
+    } else if has("index.lock") || has("another git process") {
+        GitErrorKind::Locked
+    } else if has("could not resolve host")
+        || has("could not connect")
+        || has("connection timed out")
+        || has("connection refused")
+        || has("network is unreachable")
+        || has("unable to access")
+        || has("the remote end hung up")
+    {
+        GitErrorKind::Network
+    } else if has("already exists") {
+        GitErrorKind::AlreadyExists
+    } else if has("not found")
+        || has("unknown revision")
+        || has("bad revision")
+        || has("did not match any")
+        || has("no such remote")
+        || has("does not exist")
+        || has("not a valid")
+        || has("invalid reference")
+        || has("couldn't find remote ref")
+        || has("needed a single revision")
+    {
+        GitErrorKind::NotFound
+    } else {
+        GitErrorKind::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_common_failures() {
+        let cases = [
+            (
+                "fatal: not a git repository (or any of the parent directories): .git",
+                GitErrorKind::NotARepository,
+            ),
+            (
+                " ! [rejected]        main -> main (non-fast-forward)\nerror: failed to push some refs",
+                GitErrorKind::PushRejected,
+            ),
+            (
+                "remote: Invalid username or password.\nfatal: Authentication failed for 'https://github.com/a/b.git/'",
+                GitErrorKind::AuthenticationFailed,
+            ),
+            (
+                "CONFLICT (content): Merge conflict in a.txt\nAutomatic merge failed; fix conflicts and then commit the result.",
+                GitErrorKind::MergeConflict,
+            ),
+            (
+                "error: Your local changes to the following files would be overwritten by checkout:",
+                GitErrorKind::UncommittedChanges,
+            ),
+            (
+                "fatal: Unable to create '/r/.git/index.lock': File exists.",
+                GitErrorKind::Locked,
+            ),
+            (
+                "fatal: The current branch feat has no upstream branch.",
+                GitErrorKind::NoUpstream,
+            ),
+            (
+                "fatal: a branch named 'main' already exists",
+                GitErrorKind::AlreadyExists,
+            ),
+            (
+                "error: gpg failed to sign the data\nfatal: failed to write commit object",
+                GitErrorKind::SigningFailed,
+            ),
+            (
+                "error: pathspec 'nope' did not match any file(s) known to git",
+                GitErrorKind::NotFound,
+            ),
+            ("fatal: Needed a single revision", GitErrorKind::NotFound),
+            (
+                "fatal: the requested upstream branch 'origin/x' does not exist\n\
+                 hint: branch that already exists at the remote, you may need to",
+                GitErrorKind::NotFound,
+            ),
+        ];
+        for (stderr, kind) in cases {
+            assert_eq!(classify(stderr), kind, "{}", stderr);
+        }
+    }
+
+    #[test]
+    fn test_message_strips_prefixes_and_hints() {
+        let err = GitError::from_stderr(
+            "fatal: The current branch feat has no upstream branch.\nhint: use --set-upstream",
+        );
+        assert_eq!(
+            err.message,
+            "The current branch feat has no upstream branch."
+        );
+        assert!(err.stderr.contains("hint:"));
+    }
+}
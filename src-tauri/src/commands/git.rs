@@ -1,5 +1,7 @@
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::io::Write;
+use std::process::{Command, Stdio};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GitOutput {
@@ -9,25 +11,95 @@ pub struct GitOutput {
     pub code: i32,
 }
 
-/// Run git with the given args rooted at `repo_path`.
-/// Passing an empty string for `repo_path` skips `current_dir` (useful for
-/// global commands like `git --version`).
-/// Returns a structured output even on non-zero exit codes so callers can
-/// decide how to surface errors to the frontend.
-pub fn git_run(repo_path: &str, args: &[&str]) -> Result<GitOutput, String> {
+/// A `git` command rooted at `repo_path` (skipped when empty). Messages
+/// are forced to English: errors are classified and progress is parsed by
+/// their text.
+pub fn git_command(repo_path: &str) -> Command {
     let mut cmd = Command::new("git");
     if !repo_path.is_empty() {
         cmd.current_dir(repo_path);
     }
-    let output = cmd
+    cmd.env("LC_ALL", "C").env("LANGUAGE", "C");
+    cmd
+}
+
+/// Run git with the given args rooted at `repo_path`.
+/// Passing an empty string for `repo_path` skips `current_dir` (useful for
+/// global commands like `git --version`).
+/// Returns a structured output even on non-zero exit codes so callers can
+/// decide how to surface errors to the frontend (usually via
+/// [`GitError::from_output`]).
+pub fn git_run(repo_path: &str, args: &[&str]) -> GitResult<GitOutput> {
+    let output = git_command(repo_path)
+        .args(args)
+        .output()
+        .map_err(spawn_error)?;
+
+    Ok(GitOutput {
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        success: output.status.success(),
+        code: output.status.code().unwrap_or(-1),
+    })
+}
+
+/// Error for when the git process can't be started at all.
+pub fn spawn_error(e: std::io::Error) -> GitError {
+    GitError::new(
+        GitErrorKind::GitNotFound,
+        format!(
+            "Failed to execute git: {}. Is git installed and available in PATH?",
+            e
+        ),
+    )
+}
+
+/// Quote `s` for the POSIX shell git uses to run editors, hooks and
+/// rebase `exec` lines (Git for Windows ships one too).
+pub fn sh_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Like [`git_run`], with extra environment variables (e.g. editor
+/// stand-ins so git never waits for an interactive editor).
+pub fn git_run_with_env(
+    repo_path: &str,
+    args: &[&str],
+    envs: &[(&str, &str)],
+) -> GitResult<GitOutput> {
+    let output = git_command(repo_path)
         .args(args)
+        .envs(envs.iter().copied())
         .output()
-        .map_err(|e| {
-            format!(
-                "Failed to execute git: {}. Is git installed and available in PATH?",
-                e
-            )
-        })?;
+        .map_err(spawn_error)?;
+
+    Ok(GitOutput {
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        success: output.status.success(),
+        code: output.status.code().unwrap_or(-1),
+    })
+}
+
+/// Like [`git_run`], but writes `input` to git's stdin (e.g. a patch for
+/// `git apply -`).
+pub fn git_run_with_stdin(repo_path: &str, args: &[&str], input: &str) -> GitResult<GitOutput> {
+    let mut child = git_command(repo_path)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_error)?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| format!("Failed to write to git stdin: {}", e))?;
+        // Dropping stdin closes the pipe so git sees EOF.
+    }
+
+    let output = child.wait_with_output()?;
 
     Ok(GitOutput {
         stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
@@ -39,14 +111,11 @@ pub fn git_run(repo_path: &str, args: &[&str]) -> Result<GitOutput, String> {
 
 /// Tauri command: verify git is available and return its version string.
 #[tauri::command]
-pub fn git_version() -> Result<String, String> {
+pub fn git_version() -> GitResult<String> {
     let out = git_run("", &["--version"])?;
     if out.success {
         Ok(out.stdout.trim().to_string())
     } else {
-        Err(format!(
-            "git exited with code {}: {}",
-            out.code, out.stderr
-        ))
+        Err(GitError::from_output(&out))
     }
 }
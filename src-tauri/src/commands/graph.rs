@@ -0,0 +1,432 @@
+use crate::commands::diff::CommitInfo;
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
+use crate::commands::git::git_run;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefKind {
+    /// Detached HEAD
+    Head,
+    Branch,
+    RemoteBranch,
+    Tag,
+    Other,
+}
+
+/// A ref pointing at a commit, e.g. `main`, `origin/main`, `v1.2.0`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GraphRef {
+    /// Short name without `refs/heads/` etc.
+    pub name: String,
+    pub kind: RefKind,
+    /// Branch currently checked out (`HEAD -> main`)
+    pub is_current: bool,
+}
+
+/// Line segment from this row down to the next one.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GraphEdge {
+    pub from_lane: usize,
+    pub to_lane: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphCommit {
+    #[serde(flatten)]
+    pub commit: CommitInfo,
+    pub parents: Vec<String>,
+    pub refs: Vec<GraphRef>,
+    /// Column of this commit's node
+    pub lane: usize,
+    /// Every line leaving this row: lanes passing through plus the edges to
+    /// this commit's parents
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphPage {
+    pub commits: Vec<GraphCommit>,
+    /// Pass to the next `get_graph_log` call; None on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Lane assignment state: the commit each column is waiting for.
+/// Invariant: a hash is expected by at most one lane.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Lanes(Vec<Option<String>>);
+
+impl Lanes {
+    fn find(&self, hash: &str) -> Option<usize> {
+        self.0.iter().position(|l| l.as_deref() == Some(hash))
+    }
+
+    fn alloc(&mut self, hash: &str) -> usize {
+        match self.0.iter().position(Option::is_none) {
+            Some(i) => {
+                self.0[i] = Some(hash.to_string());
+                i
+            }
+            None => {
+                self.0.push(Some(hash.to_string()));
+                self.0.len() - 1
+            }
+        }
+    }
+
+    /// Place `hash` and route its parents; returns (lane, edges).
+    fn place(&mut self, hash: &str, parents: &[String]) -> (usize, Vec<GraphEdge>) {
+        let lane = match self.find(hash) {
+            Some(i) => i,
+            // Branch tip nobody was waiting for
+            None => self.alloc(hash),
+        };
+        self.0[lane] = None;
+        // Lanes that continue from the previous row, not opened by this commit
+        let mut passing: Vec<GraphEdge> = (0..self.0.len())
+            .filter(|&i| self.0[i].is_some())
+            .map(|i| GraphEdge {
+                from_lane: i,
+                to_lane: i,
+            })
+            .collect();
+
+        let mut parent_edges = Vec::with_capacity(parents.len());
+        for (i, parent) in parents.iter().enumerate() {
+            let to = match self.find(parent) {
+                // A lane further right waits for our first parent: pull it
+                // into ours so the mainline stays on the left
+                Some(existing) if i == 0 && existing > lane => {
+                    self.0[existing] = None;
+                    self.0[lane] = Some(parent.clone());
+                    for edge in passing.iter_mut().filter(|e| e.from_lane == existing) {
+                        edge.to_lane = lane;
+                    }
+                    lane
+                }
+                // Another lane already leads there: converge into it
+                Some(existing) => existing,
+                None if i == 0 => {
+                    self.0[lane] = Some(parent.clone());
+                    lane
+                }
+                None => self.alloc(parent),
+            };
+            parent_edges.push(GraphEdge {
+                from_lane: lane,
+                to_lane: to,
+            });
+        }
+
+        while matches!(self.0.last(), Some(None)) {
+            self.0.pop();
+        }
+
+        passing.extend(parent_edges);
+        (lane, passing)
+    }
+
+    /// `<hash>,,<hash>` — empty entries are free lanes.
+    fn encode(&self) -> String {
+        self.0
+            .iter()
+            .map(|l| l.as_deref().unwrap_or(""))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn decode(s: &str) -> Self {
+        if s.is_empty() {
+            return Self::default();
+        }
+        Lanes(
+            s.split(',')
+                .map(|h| (!h.is_empty()).then(|| h.to_string()))
+                .collect(),
+        )
+    }
+}
+
+/// Parse a `%D` decoration list produced with `--decorate=full`.
+fn parse_decorations(decorations: &str) -> Vec<GraphRef> {
+    let mut refs = Vec::new();
+    for item in decorations
+        .split(", ")
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        if let Some(branch) = item.strip_prefix("HEAD -> ") {
+            refs.push(GraphRef {
+                name: branch.trim_start_matches("refs/heads/").to_string(),
+                kind: RefKind::Branch,
+                is_current: true,
+            });
+            continue;
+        }
+        let full = item.strip_prefix("tag: ").unwrap_or(item);
+        let (name, kind) = if full == "HEAD" {
+            ("HEAD", RefKind::Head)
+        } else if let Some(n) = full.strip_prefix("refs/heads/") {
+            (n, RefKind::Branch)
+        } else if let Some(n) = full.strip_prefix("refs/remotes/") {
+            (n, RefKind::RemoteBranch)
+        } else if let Some(n) = full.strip_prefix("refs/tags/") {
+            (n, RefKind::Tag)
+        } else {
+            (full, RefKind::Other)
+        };
+        refs.push(GraphRef {
+            name: name.to_string(),
+            kind,
+            is_current: false,
+        });
+    }
+    refs
+}
+
+/// Raw log record before lane assignment.
+struct LogRecord {
+    commit: CommitInfo,
+    parents: Vec<String>,
+    refs: Vec<GraphRef>,
+}
+
+fn parse_graph_records(output: &str) -> Vec<LogRecord> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.trim_end_matches('\n').splitn(7, '\x1f').collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            Some(LogRecord {
+                commit: CommitInfo {
+                    hash: fields[0].to_string(),
+                    short_hash: fields[1].to_string(),
+                    author: fields[3].to_string(),
+                    date: fields[4].split('T').next().unwrap_or(fields[4]).to_string(),
+                    message: fields[6].to_string(),
+                },
+                parents: fields[2].split_whitespace().map(str::to_string).collect(),
+                refs: parse_decorations(fields[5]),
+            })
+        })
+        .collect()
+}
+
+fn stale_cursor() -> GitError {
+    GitError::new(
+        GitErrorKind::NotFound,
+        "History changed since the previous page; reload the graph",
+    )
+}
+
+/// One page of the commit graph in topological order. Pass the returned
+/// `next_cursor` to continue; lanes stay consistent across pages.
+/// `all_branches` includes every branch, remote branch and tag instead of
+/// only HEAD.
+#[tauri::command]
+pub async fn get_graph_log(
+    repo_path: String,
+    cursor: Option<String>,
+    page_size: usize,
+    all_branches: bool,
+) -> GitResult<GraphPage> {
+    let page_size = page_size.max(1);
+
+    // Cursor: "<offset>.<last hash>.<lanes>"
+    let (offset, last_hash, mut lanes) = match cursor.as_deref() {
+        None | Some("") => (0usize, None, Lanes::default()),
+        Some(c) => {
+            let mut parts = c.splitn(3, '.');
+            let offset = parts
+                .next()
+                .and_then(|o| o.parse().ok())
+                .ok_or_else(|| GitError::from(format!("Invalid graph cursor {:?}", c)))?;
+            let last = parts.next().unwrap_or("").to_string();
+            let lanes = Lanes::decode(parts.next().unwrap_or(""));
+            (offset, Some(last), lanes)
+        }
+    };
+
+    // Re-read the last commit of the previous page to detect rewritten history
+    let skip = format!(
+        "--skip={}",
+        offset.saturating_sub(last_hash.is_some() as usize)
+    );
+    let count = format!("-n{}", page_size + 1 + last_hash.is_some() as usize);
+    let mut args = vec![
+        "log",
+        "--topo-order",
+        "--decorate=full",
+        "--format=%x1e%H%x1f%h%x1f%P%x1f%an%x1f%ai%x1f%D%x1f%s",
+        &skip,
+        &count,
+    ];
+    if all_branches {
+        args.extend_from_slice(&["--exclude=refs/stash", "--all"]);
+    }
+    let out = git_run(&repo_path, &args)?;
+    if !out.success {
+        let err = GitError::from_output(&out);
+        if err.kind == GitErrorKind::EmptyRepository {
+            return Ok(GraphPage {
+                commits: vec![],
+                next_cursor: None,
+            });
+        }
+        return Err(err);
+    }
+
+    let mut records = parse_graph_records(&out.stdout).into_iter();
+    if let Some(ref last) = last_hash {
+        match records.next() {
+            Some(r) if &r.commit.hash == last => {}
+            _ => return Err(stale_cursor()),
+        }
+    }
+
+    let records: Vec<LogRecord> = records.collect();
+    let has_more = records.len() > page_size;
+    let commits: Vec<GraphCommit> = records
+        .into_iter()
+        .take(page_size)
+        .map(|r| {
+            let (lane, edges) = lanes.place(&r.commit.hash, &r.parents);
+            GraphCommit {
+                commit: r.commit,
+                parents: r.parents,
+                refs: r.refs,
+                lane,
+                edges,
+            }
+        })
+        .collect();
+
+    let next_cursor = match commits.last() {
+        Some(last) if has_more => Some(format!(
+            "{}.{}.{}",
+            offset + commits.len(),
+            last.commit.hash,
+            lanes.encode()
+        )),
+        _ => None,
+    };
+    Ok(GraphPage {
+        commits,
+        next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(hashes: &[&str]) -> Vec<String> {
+        hashes.iter().map(|h| h.to_string()).collect()
+    }
+
+    #[test]
+    fn test_lanes_branch_and_merge() {
+        // M merges B into A; both branch off R
+        //   M
+        //   |\
+        //   A B
+        //   |/
+        //   R
+        let mut lanes = Lanes::default();
+        let (lane, edges) = lanes.place("M", &p(&["A", "B"]));
+        assert_eq!(lane, 0);
+        assert_eq!(
+            edges,
+            vec![
+                GraphEdge {
+                    from_lane: 0,
+                    to_lane: 0
+                },
+                GraphEdge {
+                    from_lane: 0,
+                    to_lane: 1
+                }
+            ]
+        );
+
+        let (lane, edges) = lanes.place("A", &p(&["R"]));
+        assert_eq!(lane, 0);
+        assert_eq!(
+            edges,
+            vec![
+                GraphEdge {
+                    from_lane: 1,
+                    to_lane: 1
+                },
+                GraphEdge {
+                    from_lane: 0,
+                    to_lane: 0
+                }
+            ]
+        );
+
+        // B's parent R is already expected by lane 0: converge and free lane 1
+        let (lane, edges) = lanes.place("B", &p(&["R"]));
+        assert_eq!(lane, 1);
+        assert_eq!(
+            edges,
+            vec![
+                GraphEdge {
+                    from_lane: 0,
+                    to_lane: 0
+                },
+                GraphEdge {
+                    from_lane: 1,
+                    to_lane: 0
+                }
+            ]
+        );
+        assert_eq!(lanes.encode(), "R");
+
+        let round_trip =
+            Lanes::decode(&Lanes(vec![Some("a".into()), None, Some("b".into())]).encode());
+        assert_eq!(round_trip.0, vec![Some("a".into()), None, Some("b".into())]);
+    }
+
+    #[test]
+    fn test_first_parent_pulled_into_left_lane() {
+        // Side branch B was walked first, so lane 1 already waits for R
+        let mut lanes = Lanes(vec![Some("A".into()), Some("R".into())]);
+        let (lane, edges) = lanes.place("A", &p(&["R"]));
+        assert_eq!(lane, 0);
+        assert_eq!(
+            edges,
+            vec![
+                GraphEdge {
+                    from_lane: 1,
+                    to_lane: 0
+                },
+                GraphEdge {
+                    from_lane: 0,
+                    to_lane: 0
+                }
+            ]
+        );
+        assert_eq!(lanes.encode(), "R");
+    }
+
+    #[test]
+    fn test_parse_decorations() {
+        let refs = parse_decorations(
+            "HEAD -> refs/heads/main, tag: refs/tags/v1.0.0, refs/remotes/origin/main",
+        );
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].name, "main");
+        assert!(refs[0].is_current);
+        assert_eq!(
+            (refs[1].name.as_str(), refs[1].kind),
+            ("v1.0.0", RefKind::Tag)
+        );
+        assert_eq!(
+            (refs[2].name.as_str(), refs[2].kind),
+            ("origin/main", RefKind::RemoteBranch)
+        );
+    }
+}
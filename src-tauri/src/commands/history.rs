@@ -0,0 +1,432 @@
+use crate::commands::diff::CommitInfo;
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
+use crate::commands::git::git_run;
+use serde::{Deserialize, Serialize};
+
+/// Filters for `search_history`. Every field is optional; an empty query
+/// lists HEAD's history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryQuery {
+    /// Number of matching commits to skip (the previous page's `next_skip`)
+    pub skip: usize,
+    pub limit: usize,
+    /// Regex matched against author name and email
+    pub author: Option<String>,
+    /// Anything `git log --since` accepts, e.g. "2024-01-31" or "2 weeks ago"
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// Regex matched against the full commit message
+    pub message: Option<String>,
+    pub ignore_case: bool,
+    /// Only commits touching these paths
+    pub paths: Vec<String>,
+    /// Follow renames (single path only)
+    pub follow: bool,
+    /// Commits that add or remove this string (`-S`), or regex with
+    /// `pickaxe_regex` (`-G`, matches changed lines)
+    pub pickaxe: Option<String>,
+    pub pickaxe_regex: bool,
+    /// e.g. "main..feature" or "v1.0.0..HEAD"; defaults to HEAD
+    pub revision_range: Option<String>,
+    /// Search all branches and tags instead of `revision_range`
+    pub all_refs: bool,
+}
+
+impl Default for HistoryQuery {
+    fn default() -> Self {
+        Self {
+            skip: 0,
+            limit: 100,
+            author: None,
+            since: None,
+            until: None,
+            message: None,
+            ignore_case: false,
+            paths: Vec::new(),
+            follow: false,
+            pickaxe: None,
+            pickaxe_regex: false,
+            revision_range: None,
+            all_refs: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    #[serde(flatten)]
+    pub commit: CommitInfo,
+    /// Message body after the subject line
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryPage {
+    pub commits: Vec<HistoryEntry>,
+    /// `skip` for the next page; None when there are no more matches
+    pub next_skip: Option<usize>,
+}
+
+/// Build `git log` arguments for `query`, requesting one extra commit so
+/// the caller can tell whether another page exists.
+fn history_args(query: &HistoryQuery) -> GitResult<Vec<String>> {
+    let mut args = vec![
+        "log".to_string(),
+        "--format=%x1e%H%x1f%h%x1f%an%x1f%ai%x1f%s%x1f%b".to_string(),
+        format!("--skip={}", query.skip),
+        format!("-n{}", query.limit.max(1) + 1),
+    ];
+
+    let mut push_opt = |flag: &str, value: &Option<String>| {
+        if let Some(v) = value.as_deref().filter(|v| !v.is_empty()) {
+            args.push(format!("{}={}", flag, v));
+        }
+    };
+    push_opt("--author", &query.author);
+    push_opt("--since", &query.since);
+    push_opt("--until", &query.until);
+    push_opt("--grep", &query.message);
+
+    if query.ignore_case {
+        args.push("--regexp-ignore-case".to_string());
+    }
+    if query.author.is_some() || query.message.is_some() {
+        args.push("--extended-regexp".to_string());
+    }
+    if let Some(p) = query.pickaxe.as_deref().filter(|p| !p.is_empty()) {
+        let flag = if query.pickaxe_regex { "-G" } else { "-S" };
+        args.push(format!("{}{}", flag, p));
+    }
+    if query.follow {
+        if query.paths.len() != 1 {
+            return Err("Following renames requires exactly one path".into());
+        }
+        args.push("--follow".to_string());
+    }
+
+    if query.all_refs {
+        args.push("--exclude=refs/stash".to_string());
+        args.push("--all".to_string());
+    } else if let Some(range) = query.revision_range.as_deref().filter(|r| !r.is_empty()) {
+        // Don't let a range be interpreted as an option
+        if range.starts_with('-') {
+            return Err(format!("Invalid revision range {:?}", range).into());
+        }
+        args.push(range.to_string());
+    }
+
+    args.push("--".to_string());
+    args.extend(query.paths.iter().cloned());
+    Ok(args)
+}
+
+fn parse_history_records(output: &str) -> Vec<HistoryEntry> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.splitn(6, '\x1f').collect();
+            if fields.len() < 6 {
+                return None;
+            }
+            Some(HistoryEntry {
+                commit: CommitInfo {
+                    hash: fields[0].to_string(),
+                    short_hash: fields[1].to_string(),
+                    author: fields[2].to_string(),
+                    date: fields[3].split('T').next().unwrap_or(fields[3]).to_string(),
+                    message: fields[4].to_string(),
+                },
+                body: fields[5].trim_end().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Search commit history with filters and skip-based pagination.
+#[tauri::command]
+pub async fn search_history(repo_path: String, query: HistoryQuery) -> GitResult<HistoryPage> {
+    let args = history_args(&query)?;
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    let out = git_run(&repo_path, &arg_refs)?;
+    if !out.success {
+        let err = GitError::from_output(&out);
+        if err.kind == GitErrorKind::EmptyRepository {
+            return Ok(HistoryPage {
+                commits: vec![],
+                next_skip: None,
+            });
+        }
+        return Err(err);
+    }
+
+    let limit = query.limit.max(1);
+    let mut commits = parse_history_records(&out.stdout);
+    let next_skip = if commits.len() > limit {
+        commits.truncate(limit);
+        Some(query.skip + limit)
+    } else {
+        None
+    };
+    Ok(HistoryPage { commits, next_skip })
+}
+
+/// Author or committer identity with a timestamp.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitPerson {
+    pub name: String,
+    pub email: String,
+    /// Unix timestamp (seconds)
+    pub timestamp: i64,
+    /// Strict ISO 8601 with the original timezone, e.g. "2024-05-01T14:03:22+02:00"
+    pub date: String,
+}
+
+/// A `Key: value` trailer such as `Signed-off-by` or `Co-authored-by`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommitTrailer {
+    pub key: String,
+    pub value: String,
+}
+
+/// Result of GPG/SSH signature verification (`%G?`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    Unsigned,
+    Good,
+    Bad,
+    /// Good signature from a key of unknown validity
+    UnknownValidity,
+    /// Good signature that has expired
+    Expired,
+    /// Good signature made by an expired key
+    ExpiredKey,
+    /// Good signature made by a revoked key
+    RevokedKey,
+    /// Signed, but the key is missing or gpg/ssh-keygen isn't configured
+    CannotCheck,
+}
+
+impl SignatureStatus {
+    fn from_code(code: &str) -> Self {
+        match code {
+            "G" => SignatureStatus::Good,
+            "B" => SignatureStatus::Bad,
+            "U" => SignatureStatus::UnknownValidity,
+            "X" => SignatureStatus::Expired,
+            "Y" => SignatureStatus::ExpiredKey,
+            "R" => SignatureStatus::RevokedKey,
+            "E" => SignatureStatus::CannotCheck,
+            _ => SignatureStatus::Unsigned,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitSignature {
+    pub status: SignatureStatus,
+    /// Signer name/identity as reported by gpg or ssh-keygen
+    pub signer: Option<String>,
+    pub key: Option<String>,
+}
+
+/// Per-file line counts; None for binary files.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommitFileStat {
+    pub path: String,
+    /// Previous path for renames and copies
+    pub old_path: Option<String>,
+    pub additions: Option<u32>,
+    pub deletions: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitDetails {
+    pub hash: String,
+    pub short_hash: String,
+    pub parents: Vec<String>,
+    pub author: CommitPerson,
+    pub committer: CommitPerson,
+    pub subject: String,
+    /// Message after the subject line, trailers included
+    pub body: String,
+    pub trailers: Vec<CommitTrailer>,
+    pub signature: CommitSignature,
+    /// Changes against the first parent (empty tree for root commits)
+    pub files: Vec<CommitFileStat>,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+/// Parse `--numstat -z` output. Renames are `adds\tdels\t\0old\0new\0`.
+pub(crate) fn parse_numstat_z(output: &str) -> Vec<CommitFileStat> {
+    let mut stats = Vec::new();
+    let mut tokens = output.split('\0');
+    while let Some(token) = tokens.next() {
+        let mut parts = token.splitn(3, '\t');
+        let (Some(adds), Some(dels), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let (path, old_path) = if path.is_empty() {
+            let old = tokens.next().unwrap_or_default().to_string();
+            let new = tokens.next().unwrap_or_default().to_string();
+            (new, Some(old))
+        } else {
+            (path.to_string(), None)
+        };
+        stats.push(CommitFileStat {
+            path,
+            old_path,
+            additions: adds.parse().ok(),
+            deletions: dels.parse().ok(),
+        });
+    }
+    stats
+}
+
+fn parse_trailers(text: &str) -> Vec<CommitTrailer> {
+    text.lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some(CommitTrailer {
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Full metadata for one commit: identities, message, trailers, parents,
+/// signature verification and per-file stats.
+#[tauri::command]
+pub async fn get_commit_details(repo_path: String, hash: String) -> GitResult<CommitDetails> {
+    if hash.starts_with('-') {
+        return Err(format!("Invalid commit {:?}", hash).into());
+    }
+    let out = git_run(
+        &repo_path,
+        &[
+            "show",
+            "-s",
+            "--format=%H%x1f%h%x1f%P%x1f%an%x1f%ae%x1f%at%x1f%aI%x1f%cn%x1f%ce%x1f%ct%x1f%cI%x1f%G?%x1f%GS%x1f%GK%x1f%(trailers:unfold,only)%x1f%s%x1f%b",
+            &hash,
+            "--",
+        ],
+    )?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+
+    let f: Vec<&str> = out.stdout.splitn(17, '\x1f').collect();
+    if f.len() < 17 {
+        return Err(format!("Unexpected git show output for {}", hash).into());
+    }
+    let person = |name: &str, email: &str, ts: &str, date: &str| CommitPerson {
+        name: name.to_string(),
+        email: email.to_string(),
+        timestamp: ts.parse().unwrap_or(0),
+        date: date.to_string(),
+    };
+    let non_empty = |s: &str| (!s.trim().is_empty()).then(|| s.trim().to_string());
+    let parents: Vec<String> = f[2].split_whitespace().map(str::to_string).collect();
+
+    let stat_out = match parents.first() {
+        // Root commit: diff against the empty tree
+        None => git_run(
+            &repo_path,
+            &[
+                "diff-tree",
+                "--root",
+                "-r",
+                "-M",
+                "--no-commit-id",
+                "--numstat",
+                "-z",
+                f[0],
+            ],
+        )?,
+        Some(first) => git_run(&repo_path, &["diff", "-M", "--numstat", "-z", first, f[0]])?,
+    };
+    if !stat_out.success {
+        return Err(GitError::from_output(&stat_out));
+    }
+    let files = parse_numstat_z(&stat_out.stdout);
+
+    Ok(CommitDetails {
+        hash: f[0].to_string(),
+        short_hash: f[1].to_string(),
+        author: person(f[3], f[4], f[5], f[6]),
+        committer: person(f[7], f[8], f[9], f[10]),
+        signature: CommitSignature {
+            status: SignatureStatus::from_code(f[11]),
+            signer: non_empty(f[12]),
+            key: non_empty(f[13]),
+        },
+        trailers: parse_trailers(f[14]),
+        subject: f[15].to_string(),
+        body: f[16].trim_end().to_string(),
+        additions: files.iter().filter_map(|s| s.additions).sum(),
+        deletions: files.iter().filter_map(|s| s.deletions).sum(),
+        files,
+        parents,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_args() {
+        let query = HistoryQuery {
+            skip: 50,
+            limit: 25,
+            author: Some("alice".into()),
+            message: Some("^fix".into()),
+            ignore_case: true,
+            pickaxe: Some("parse_diff".into()),
+            revision_range: Some("v1.0.0..HEAD".into()),
+            paths: vec!["src".into()],
+            ..Default::default()
+        };
+        let args = history_args(&query).unwrap();
+        assert_eq!(
+            &args[2..],
+            [
+                "--skip=50",
+                "-n26",
+                "--author=alice",
+                "--grep=^fix",
+                "--regexp-ignore-case",
+                "--extended-regexp",
+                "-Sparse_diff",
+                "v1.0.0..HEAD",
+                "--",
+                "src",
+            ]
+        );
+
+        let bad = HistoryQuery {
+            revision_range: Some("--output=/tmp/x".into()),
+            ..Default::default()
+        };
+        assert!(history_args(&bad).is_err());
+    }
+
+    #[test]
+    fn test_parse_numstat_z_with_rename_and_binary() {
+        let stats = parse_numstat_z(concat!(
+            "3\t1\tsrc/a.rs\0",
+            "-\t-\timg.png\0",
+            "0\t0\t\0old.rs\0new.rs\0"
+        ));
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[0].path, "src/a.rs");
+        assert_eq!((stats[0].additions, stats[0].deletions), (Some(3), Some(1)));
+        assert_eq!((stats[1].additions, stats[1].deletions), (None, None));
+        assert_eq!(stats[2].path, "new.rs");
+        assert_eq!(stats[2].old_path.as_deref(), Some("old.rs"));
+    }
+}
@@ -0,0 +1,270 @@
+use crate::commands::error::{GitError, GitResult};
+use crate::commands::git::{git_run, sh_quote, GitOutput};
+use crate::commands::progress::git_run_streaming;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Hooks git runs client-side, with the operation that triggers them.
+const CLIENT_HOOKS: &[(&str, &str)] = &[
+    ("pre-commit", "commit"),
+    ("prepare-commit-msg", "commit"),
+    ("commit-msg", "commit"),
+    ("post-commit", "commit"),
+    ("pre-merge-commit", "merge"),
+    ("post-merge", "merge"),
+    ("pre-rebase", "rebase"),
+    ("post-rewrite", "rebase"),
+    ("post-checkout", "checkout"),
+    ("reference-transaction", "other"),
+    ("pre-push", "push"),
+    ("pre-auto-gc", "other"),
+    ("applypatch-msg", "other"),
+    ("pre-applypatch", "other"),
+    ("post-applypatch", "other"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookInfo {
+    /// e.g. "pre-commit"
+    pub name: String,
+    pub path: String,
+    /// "commit", "push", "merge", "rebase", "checkout" or "other"
+    pub trigger: String,
+    /// Non-executable hooks are silently skipped by git (Unix only)
+    pub executable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HooksInfo {
+    pub hooks_dir: String,
+    /// True when `core.hooksPath` redirects hooks (husky, lefthook …)
+    pub custom_hooks_path: bool,
+    pub hooks: Vec<HookInfo>,
+}
+
+/// A hook that exited non-zero and stopped the operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HookFailure {
+    pub hook: String,
+    pub exit_code: i32,
+    /// Everything git and the hook printed
+    pub output: String,
+}
+
+/// Directory git reads hooks from, honouring `core.hooksPath`.
+fn hooks_dir(repo_path: &str) -> GitResult<PathBuf> {
+    let out = git_run(repo_path, &["rev-parse", "--git-path", "hooks"])?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    let dir = PathBuf::from(out.stdout.trim());
+    Ok(if dir.is_absolute() {
+        dir
+    } else {
+        Path::new(repo_path).join(dir)
+    })
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .map(|m| m.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+fn installed_hooks(dir: &Path) -> Vec<HookInfo> {
+    CLIENT_HOOKS
+        .iter()
+        .filter_map(|(name, trigger)| {
+            let path = dir.join(name);
+            path.is_file().then(|| HookInfo {
+                name: name.to_string(),
+                path: path.to_string_lossy().into_owned(),
+                trigger: trigger.to_string(),
+                executable: is_executable(&path),
+            })
+        })
+        .collect()
+}
+
+/// List installed client-side hooks and what triggers them.
+#[tauri::command]
+pub fn list_hooks(repo_path: String) -> GitResult<HooksInfo> {
+    let dir = hooks_dir(&repo_path)?;
+    let custom = git_run(&repo_path, &["config", "--get", "core.hooksPath"])?;
+    Ok(HooksInfo {
+        hooks_dir: dir.to_string_lossy().into_owned(),
+        custom_hooks_path: custom.success && !custom.stdout.trim().is_empty(),
+        hooks: installed_hooks(&dir),
+    })
+}
+
+/// Every executable file in `dir` except git's `.sample` scripts. A wrapper
+/// directory replaces the real one for the whole command, so it has to
+/// cover hooks `CLIENT_HOOKS` doesn't know about too.
+fn executable_hooks(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut hooks: Vec<(String, PathBuf)> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?.to_string();
+            (path.is_file() && !name.ends_with(".sample") && is_executable(&path))
+                .then_some((name, path))
+        })
+        .collect();
+    hooks.sort();
+    hooks
+}
+
+static NEXT_WRAPPER: AtomicU64 = AtomicU64::new(0);
+
+/// Temporary hooks directory whose scripts run the real hooks and record
+/// their exit codes, since git itself doesn't say which hook failed.
+/// Removed on drop.
+struct HookWrappers {
+    dir: PathBuf,
+    status_file: PathBuf,
+}
+
+impl HookWrappers {
+    /// None when no executable hooks are installed.
+    fn install(repo_path: &str) -> GitResult<Option<Self>> {
+        let hooks = executable_hooks(&hooks_dir(repo_path)?);
+        if hooks.is_empty() {
+            return Ok(None);
+        }
+
+        let git_dir = git_run(repo_path, &["rev-parse", "--absolute-git-dir"])?;
+        if !git_dir.success {
+            return Err(GitError::from_output(&git_dir));
+        }
+        let dir = PathBuf::from(git_dir.stdout.trim()).join(format!(
+            "easygit-hooks-{}-{}",
+            std::process::id(),
+            NEXT_WRAPPER.fetch_add(1, Ordering::SeqCst)
+        ));
+        std::fs::create_dir_all(&dir)?;
+        let wrappers = HookWrappers {
+            status_file: dir.join("status"),
+            dir,
+        };
+
+        let status = wrappers.status_file.to_string_lossy().replace('\\', "/");
+        for (name, hook_path) in hooks {
+            let script = format!(
+                "#!/bin/sh\n{} \"$@\"\ncode=$?\necho \"{} $code\" >> {}\nexit $code\n",
+                sh_quote(&hook_path.to_string_lossy().replace('\\', "/")),
+                name,
+                sh_quote(&status)
+            );
+            let path = wrappers.dir.join(&name);
+            std::fs::write(&path, script)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+            }
+        }
+        Ok(Some(wrappers))
+    }
+
+    fn config_arg(&self) -> String {
+        format!(
+            "core.hooksPath={}",
+            self.dir.to_string_lossy().replace('\\', "/")
+        )
+    }
+
+    /// Last hook that exited non-zero, if any. git ignores the exit code
+    /// of post-* hooks, so those never explain a failure.
+    fn failure(&self, output: &str) -> Option<HookFailure> {
+        let status = std::fs::read_to_string(&self.status_file).ok()?;
+        status.lines().rev().find_map(|line| {
+            let (hook, code) = line.rsplit_once(' ')?;
+            if hook.starts_with("post-") {
+                return None;
+            }
+            let exit_code: i32 = code.trim().parse().ok()?;
+            (exit_code != 0).then(|| HookFailure {
+                hook: hook.to_string(),
+                exit_code,
+                output: output.trim().to_string(),
+            })
+        })
+    }
+}
+
+impl Drop for HookWrappers {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Run a git command that may trigger hooks, streaming all output (hook
+/// output included) as `git-progress` events. If a hook rejects the
+/// operation this fails with a `hook_failed` error carrying a
+/// [`HookFailure`]; other failures are returned as output for the caller.
+pub fn git_run_with_hooks(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    args: &[&str],
+    operation: &str,
+) -> GitResult<GitOutput> {
+    let wrappers = HookWrappers::install(repo_path)?;
+    let Some(wrappers) = wrappers else {
+        return git_run_streaming(app, repo_path, args, operation);
+    };
+
+    let config = wrappers.config_arg();
+    let mut full_args = vec!["-c", config.as_str()];
+    full_args.extend_from_slice(args);
+    let out = git_run_streaming(app, repo_path, &full_args, operation)?;
+    if out.success {
+        return Ok(out);
+    }
+
+    let output = format!("{}\n{}", out.stdout.trim(), out.stderr.trim());
+    match wrappers.failure(&output) {
+        Some(failure) => Err(GitError::hook_failed(failure)),
+        None => Ok(out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapper_status_reports_last_failure() {
+        let dir = std::env::temp_dir().join(format!("easygit-hooks-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let wrappers = HookWrappers {
+            status_file: dir.join("status"),
+            dir: dir.clone(),
+        };
+        assert_eq!(wrappers.failure("out"), None);
+
+        std::fs::write(
+            &wrappers.status_file,
+            "pre-commit 0\ncommit-msg 2\npost-checkout 1\n",
+        )
+        .unwrap();
+        let failure = wrappers.failure("bad message").unwrap();
+        assert_eq!(failure.hook, "commit-msg");
+        assert_eq!(failure.exit_code, 2);
+        assert_eq!(failure.output, "bad message");
+
+        drop(wrappers);
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,381 @@
+use crate::cache::models::{DiffLine, DiffLineKind, ParsedFileDiff};
+use crate::cache::{get_cache, parse_diff_parallel};
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
+use crate::commands::git::{git_run, git_run_with_stdin};
+use serde::{Deserialize, Serialize};
+
+/// Inclusive range of indices into `ParsedHunk.lines`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LineRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A hunk picked from the `ParsedDiff` returned by `get_diff_cached`. The
+/// commands taking these also take that diff's `diff_text` to check the
+/// indices still mean the same lines.
+/// When `line_ranges` is empty the whole hunk is used; otherwise only the
+/// added/removed lines inside those ranges are.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HunkSelection {
+    pub hunk_index: usize,
+    #[serde(default)]
+    pub line_ranges: Vec<LineRange>,
+}
+
+impl HunkSelection {
+    fn includes(&self, line_index: usize) -> bool {
+        self.line_ranges.is_empty()
+            || self
+                .line_ranges
+                .iter()
+                .any(|r| line_index >= r.start && line_index <= r.end)
+    }
+}
+
+/// Render patch lines. A line flagged `no_newline` ends its file, so it
+/// gets the marker only where it is the last line of a side: a context line
+/// that still ends the old side but not the new one (a removal kept while
+/// additions after it are picked) is split into `-` and `+`.
+fn render_hunk_lines(lines: &[(char, &DiffLine)]) -> String {
+    let last_old = lines.iter().rposition(|(m, _)| *m != '+');
+    let last_new = lines.iter().rposition(|(m, _)| *m != '-');
+    let mut body = String::new();
+    let mut push = |marker: char, content: &str, no_newline: bool| {
+        body.push(marker);
+        body.push_str(content);
+        body.push('\n');
+        if no_newline {
+            body.push_str("\\ No newline at end of file\n");
+        }
+    };
+    for (i, (marker, line)) in lines.iter().enumerate() {
+        let ends_old = line.no_newline && last_old == Some(i);
+        let ends_new = line.no_newline && last_new == Some(i);
+        match marker {
+            ' ' if ends_old != ends_new => {
+                push('-', &line.content, ends_old);
+                push('+', &line.content, ends_new);
+            }
+            '+' => push('+', &line.content, ends_new),
+            _ => push(*marker, &line.content, ends_old),
+        }
+    }
+    body
+}
+
+/// Build a patch containing only the selected hunks/lines of `file`.
+///
+/// With `reverse = false` the patch is meant to be applied forwards to the
+/// diff's old side (staging). With `reverse = true` it is applied with
+/// `git apply --reverse` against the new side (unstaging, discarding), so
+/// unselected lines have to be kept as they exist on that side instead.
+pub fn build_partial_patch(
+    file: &ParsedFileDiff,
+    selection: &[HunkSelection],
+    reverse: bool,
+) -> Option<String> {
+    let mut patch = String::new();
+    // Running difference between new and old line counts of emitted hunks;
+    // used to shift the start of the side we're not anchored to.
+    let mut offset: i64 = 0;
+
+    for (hunk_index, hunk) in file.hunks.iter().enumerate() {
+        let Some(sel) = selection.iter().find(|s| s.hunk_index == hunk_index) else {
+            continue;
+        };
+
+        let mut lines: Vec<(char, &DiffLine)> = Vec::new();
+        let mut old_count = 0u32;
+        let mut new_count = 0u32;
+        let mut has_change = false;
+
+        for (line_index, line) in hunk.lines.iter().enumerate() {
+            let selected = sel.includes(line_index);
+            let marker = match (line.kind, selected, reverse) {
+                (DiffLineKind::Context, _, _) => ' ',
+                (DiffLineKind::Added, true, _) => '+',
+                (DiffLineKind::Removed, true, _) => '-',
+                // Unselected additions only exist on the new side.
+                (DiffLineKind::Added, false, false) => continue,
+                (DiffLineKind::Added, false, true) => ' ',
+                // Unselected removals only exist on the old side.
+                (DiffLineKind::Removed, false, false) => ' ',
+                (DiffLineKind::Removed, false, true) => continue,
+            };
+
+            match marker {
+                '+' => {
+                    new_count += 1;
+                    has_change = true;
+                }
+                '-' => {
+                    old_count += 1;
+                    has_change = true;
+                }
+                _ => {
+                    old_count += 1;
+                    new_count += 1;
+                }
+            }
+
+            lines.push((marker, line));
+        }
+
+        if !has_change {
+            continue;
+        }
+        let body = render_hunk_lines(&lines);
+
+        let (old_start, new_start) = if reverse {
+            let old_start = (hunk.new_start as i64 - offset).max(0) as u32;
+            (old_start, hunk.new_start)
+        } else {
+            let new_start = (hunk.old_start as i64 + offset).max(0) as u32;
+            (hunk.old_start, new_start)
+        };
+        offset += new_count as i64 - old_count as i64;
+
+        patch.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_count, new_start, new_count
+        ));
+        patch.push_str(&body);
+    }
+
+    if patch.is_empty() {
+        return None;
+    }
+
+    let mut header = file.header.clone();
+    if !header.ends_with('\n') {
+        header.push('\n');
+    }
+    Some(header + &patch)
+}
+
+/// The hunks of a single-file diff, i.e. everything from the first `@@`
+/// line. File headers differ between `diff --no-index` (how untracked files
+/// are shown) and an intent-to-add diff, the hunks don't.
+fn hunk_text(diff: &str) -> &str {
+    if diff.starts_with("@@") {
+        return diff;
+    }
+    diff.find("\n@@").map_or("", |i| &diff[i + 1..])
+}
+
+/// Diff `file_path`, pick the selected hunks and pipe the result into
+/// `git apply` with the given extra flags. `diff_text` is the diff the
+/// selection was made on; if the file changed since, the indices may point
+/// at other lines, so nothing is applied.
+fn apply_selection(
+    repo_path: &str,
+    file_path: &str,
+    staged: bool,
+    diff_text: &str,
+    selection: &[HunkSelection],
+    reverse: bool,
+    apply_flags: &[&str],
+) -> GitResult<()> {
+    if !staged {
+        let ls_out = git_run(repo_path, &["ls-files", "--", file_path])?;
+        if ls_out.success && ls_out.stdout.trim().is_empty() {
+            if reverse {
+                return Err(
+                    format!("{} is untracked; discard the whole file instead", file_path).into(),
+                );
+            }
+            // Untracked files have no index entry to diff against; mark
+            // them intent-to-add so `git diff` shows their content as
+            // additions.
+            let add_out = git_run(repo_path, &["add", "--intent-to-add", "--", file_path])?;
+            if !add_out.success {
+                return Err(GitError::from_output(&add_out));
+            }
+        }
+    }
+
+    let diff_args: Vec<&str> = if staged {
+        vec!["diff", "--staged", "--", file_path]
+    } else {
+        vec!["diff", "--", file_path]
+    };
+    let out = git_run(repo_path, &diff_args)?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+
+    if hunk_text(&out.stdout) != hunk_text(diff_text) {
+        return Err(format!(
+            "{} changed since its diff was loaded; reload it and select again",
+            file_path
+        )
+        .into());
+    }
+
+    let parsed = parse_diff_parallel(&out.stdout);
+    let Some(file) = parsed.files.first() else {
+        return Err(GitError::new(
+            GitErrorKind::NotFound,
+            format!("No changes found for {}", file_path),
+        ));
+    };
+
+    let Some(patch) = build_partial_patch(file, selection, reverse) else {
+        return Err("Nothing selected to apply".into());
+    };
+
+    let mut args = vec!["apply", "--recount", "--whitespace=nowarn"];
+    args.extend_from_slice(apply_flags);
+    args.push("-");
+    let out = git_run_with_stdin(repo_path, &args, &patch)?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+
+    // Both sides of the diff may have changed.
+    let cache = get_cache();
+    cache.invalidate(repo_path, file_path, true);
+    cache.invalidate(repo_path, file_path, false);
+    Ok(())
+}
+
+/// Stage selected hunks/lines of a file's unstaged changes.
+#[tauri::command]
+pub fn stage_hunks(
+    repo_path: String,
+    file_path: String,
+    diff_text: String,
+    selection: Vec<HunkSelection>,
+) -> GitResult<()> {
+    apply_selection(
+        &repo_path,
+        &file_path,
+        false,
+        &diff_text,
+        &selection,
+        false,
+        &["--cached"],
+    )
+}
+
+/// Unstage selected hunks/lines of a file's staged changes.
+#[tauri::command]
+pub fn unstage_hunks(
+    repo_path: String,
+    file_path: String,
+    diff_text: String,
+    selection: Vec<HunkSelection>,
+) -> GitResult<()> {
+    apply_selection(
+        &repo_path,
+        &file_path,
+        true,
+        &diff_text,
+        &selection,
+        true,
+        &["--cached", "--reverse"],
+    )
+}
+
+/// Discard selected hunks/lines of a file's unstaged changes from the working tree.
+#[tauri::command]
+pub fn discard_hunks(
+    repo_path: String,
+    file_path: String,
+    diff_text: String,
+    selection: Vec<HunkSelection>,
+) -> GitResult<()> {
+    apply_selection(
+        &repo_path,
+        &file_path,
+        false,
+        &diff_text,
+        &selection,
+        true,
+        &["--reverse"],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/f.txt b/f.txt\n\
+index 1111111..2222222 100644\n\
+--- a/f.txt\n\
++++ b/f.txt\n\
+@@ -1,4 +1,4 @@\n\
+ a\n\
+-b\n\
++B\n\
+ c\n\
+-d\n\
++D\n";
+
+    fn file() -> ParsedFileDiff {
+        parse_diff_parallel(DIFF).files.remove(0)
+    }
+
+    #[test]
+    fn test_whole_hunk_roundtrips() {
+        let sel = vec![HunkSelection {
+            hunk_index: 0,
+            line_ranges: vec![],
+        }];
+        let patch = build_partial_patch(&file(), &sel, false).unwrap();
+        assert!(patch.contains("@@ -1,4 +1,4 @@\n a\n-b\n+B\n c\n-d\n+D\n"));
+    }
+
+    #[test]
+    fn test_line_selection_forward() {
+        // Only stage "b" → "B"; the second change stays in the worktree.
+        let sel = vec![HunkSelection {
+            hunk_index: 0,
+            line_ranges: vec![LineRange { start: 1, end: 2 }],
+        }];
+        let patch = build_partial_patch(&file(), &sel, false).unwrap();
+        assert!(patch.ends_with("@@ -1,4 +1,4 @@\n a\n-b\n+B\n c\n d\n"));
+    }
+
+    #[test]
+    fn test_line_selection_reverse() {
+        // Reverse patches keep unselected additions as context instead.
+        let sel = vec![HunkSelection {
+            hunk_index: 0,
+            line_ranges: vec![LineRange { start: 4, end: 5 }],
+        }];
+        let patch = build_partial_patch(&file(), &sel, true).unwrap();
+        assert!(patch.ends_with("@@ -1,4 +1,4 @@\n a\n B\n c\n-d\n+D\n"));
+    }
+
+    #[test]
+    fn test_no_newline_marker_follows_each_side() {
+        // "x" without a newline becomes "x\ny\n"; staging only "+y" keeps
+        // "x" on the old side, where it still ends the file.
+        let diff = "diff --git a/f b/f\n\
+--- a/f\n\
++++ b/f\n\
+@@ -1 +1,2 @@\n\
+-x\n\
+\\ No newline at end of file\n\
++x\n\
++y\n";
+        let file = parse_diff_parallel(diff).files.remove(0);
+        let sel = vec![HunkSelection {
+            hunk_index: 0,
+            line_ranges: vec![LineRange { start: 2, end: 2 }],
+        }];
+        let patch = build_partial_patch(&file, &sel, false).unwrap();
+        assert!(patch.ends_with("@@ -1,1 +1,2 @@\n-x\n\\ No newline at end of file\n+x\n+y\n"));
+    }
+
+    #[test]
+    fn test_empty_selection() {
+        let sel = vec![HunkSelection {
+            hunk_index: 0,
+            line_ranges: vec![LineRange { start: 0, end: 0 }],
+        }];
+        assert!(build_partial_patch(&file(), &sel, false).is_none());
+    }
+}
@@ -0,0 +1,555 @@
+use crate::cache::conflict_parser::{parse_conflicts, resolve_conflicts};
+use crate::cache::models::{BlockResolution, ConflictBlock};
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
+use crate::commands::git::{git_run, git_run_with_env, GitOutput};
+use crate::commands::remote::check_arg;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A multi-step operation git has paused in the middle of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InProgressKind {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+}
+
+impl InProgressKind {
+    fn command(self) -> &'static str {
+        match self {
+            InProgressKind::Merge => "merge",
+            InProgressKind::Rebase => "rebase",
+            InProgressKind::CherryPick => "cherry-pick",
+            InProgressKind::Revert => "revert",
+        }
+    }
+}
+
+/// State of an in-progress merge/rebase/cherry-pick/revert, read from `.git`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoState {
+    pub kind: InProgressKind,
+    /// Branch being rebased (rebase only)
+    pub head_name: Option<String>,
+    /// Commit being merged, picked or reverted; `onto` commit for rebases
+    pub target: Option<String>,
+    /// 1-based step of a rebase. Cherry-picks and reverts leave these unset:
+    /// git keeps only the remaining queue, not how long it was.
+    pub current_step: Option<u32>,
+    pub total_steps: Option<u32>,
+    pub conflicted_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStatus {
+    UpToDate,
+    FastForward,
+    /// Finished and committed
+    Completed,
+    /// Stopped with conflicts that need resolving
+    Conflicts,
+    /// Stopped without conflicts (e.g. rebase `edit` step)
+    Stopped,
+}
+
+/// Result of merge, rebase, cherry-pick and revert commands. Conflicts are
+/// reported here rather than as an error so the UI can go straight to the
+/// conflict list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeOutcome {
+    pub status: MergeStatus,
+    pub message: String,
+    pub conflicts: Vec<String>,
+    pub state: Option<RepoState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+}
+
+/// One unmerged path with the index stage contents.
+/// Stage 1 is the common ancestor, 2 is "ours" (HEAD), 3 is "theirs".
+/// During a rebase, "ours" is the branch being rebased onto.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictFile {
+    pub path: String,
+    /// "both_modified", "both_added", "deleted_by_us", "deleted_by_them",
+    /// "added_by_us", "added_by_them", "both_deleted"
+    pub conflict_type: String,
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+    /// Working-tree content (with conflict markers), if the file exists
+    pub working: Option<String>,
+}
+
+pub(crate) fn git_dir(repo_path: &str) -> GitResult<PathBuf> {
+    let out = git_run(repo_path, &["rev-parse", "--absolute-git-dir"])?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    Ok(PathBuf::from(out.stdout.trim()))
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn read_number(path: &Path) -> Option<u32> {
+    read_trimmed(path).and_then(|s| s.parse().ok())
+}
+
+/// Paths with unmerged index entries.
+pub fn conflicted_paths(repo_path: &str) -> GitResult<Vec<String>> {
+    let out = git_run(repo_path, &["diff", "--name-only", "--diff-filter=U", "-z"])?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    Ok(out
+        .stdout
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Detect an in-progress merge/rebase/cherry-pick/revert from the git dir.
+pub fn read_repo_state(repo_path: &str) -> GitResult<Option<RepoState>> {
+    let dir = git_dir(repo_path)?;
+
+    let rebase_merge = dir.join("rebase-merge");
+    let rebase_apply = dir.join("rebase-apply");
+    let mut state = if rebase_merge.is_dir() {
+        RepoState {
+            kind: InProgressKind::Rebase,
+            head_name: read_trimmed(&rebase_merge.join("head-name"))
+                .map(|h| h.trim_start_matches("refs/heads/").to_string()),
+            target: read_trimmed(&rebase_merge.join("onto")),
+            current_step: read_number(&rebase_merge.join("msgnum")),
+            total_steps: read_number(&rebase_merge.join("end")),
+            conflicted_files: Vec::new(),
+        }
+    } else if rebase_apply.join("rebasing").exists() {
+        RepoState {
+            kind: InProgressKind::Rebase,
+            head_name: read_trimmed(&rebase_apply.join("head-name"))
+                .map(|h| h.trim_start_matches("refs/heads/").to_string()),
+            target: read_trimmed(&rebase_apply.join("onto")),
+            current_step: read_number(&rebase_apply.join("next")),
+            total_steps: read_number(&rebase_apply.join("last")),
+            conflicted_files: Vec::new(),
+        }
+    } else {
+        let (kind, head_file) = if dir.join("MERGE_HEAD").exists() {
+            (InProgressKind::Merge, "MERGE_HEAD")
+        } else if dir.join("CHERRY_PICK_HEAD").exists() {
+            (InProgressKind::CherryPick, "CHERRY_PICK_HEAD")
+        } else if dir.join("REVERT_HEAD").exists() {
+            (InProgressKind::Revert, "REVERT_HEAD")
+        } else {
+            return Ok(None);
+        };
+        RepoState {
+            kind,
+            head_name: None,
+            target: read_trimmed(&dir.join(head_file)),
+            current_step: None,
+            total_steps: None,
+            conflicted_files: Vec::new(),
+        }
+    };
+
+    state.conflicted_files = conflicted_paths(repo_path)?;
+    Ok(Some(state))
+}
+
+/// Turn the output of a merge-like command into a [`MergeOutcome`].
+/// Failures that left the repo in a conflicted/stopped state are reported
+/// as outcomes; anything else is an error.
+pub fn outcome_from_output(repo_path: &str, out: &GitOutput) -> GitResult<MergeOutcome> {
+    let message = format!("{}\n{}", out.stdout.trim(), out.stderr.trim())
+        .trim()
+        .to_string();
+    let state = read_repo_state(repo_path)?;
+
+    if out.success {
+        let lower = message.to_lowercase();
+        let status = if state.is_some() {
+            MergeStatus::Stopped
+        } else if lower.contains("already up to date") || lower.contains("is up to date") {
+            MergeStatus::UpToDate
+        } else if lower.contains("fast-forward") {
+            MergeStatus::FastForward
+        } else {
+            MergeStatus::Completed
+        };
+        return Ok(MergeOutcome {
+            status,
+            message,
+            conflicts: state
+                .as_ref()
+                .map(|s| s.conflicted_files.clone())
+                .unwrap_or_default(),
+            state,
+        });
+    }
+
+    // `--no-commit` cherry-picks/reverts leave conflicts without any state file
+    let conflicts = match state {
+        Some(ref s) => s.conflicted_files.clone(),
+        None => conflicted_paths(repo_path)?,
+    };
+    if state.is_none() && conflicts.is_empty() {
+        return Err(GitError::from_output(out));
+    }
+    Ok(MergeOutcome {
+        status: if conflicts.is_empty() {
+            MergeStatus::Stopped
+        } else {
+            MergeStatus::Conflicts
+        },
+        message,
+        conflicts,
+        state,
+    })
+}
+
+/// Refuse to start a merge-like command while another one is unfinished,
+/// so its conflicts aren't mistaken for the new command's.
+pub fn ensure_no_operation(repo_path: &str) -> GitResult<()> {
+    match read_repo_state(repo_path)? {
+        Some(state) => Err(format!(
+            "A {} is already in progress; continue or abort it first",
+            state.kind.command()
+        )
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Merge `branch` into the current branch.
+#[tauri::command]
+pub fn merge_branch(
+    repo_path: String,
+    branch: String,
+    no_ff: bool,
+    message: Option<String>,
+) -> GitResult<MergeOutcome> {
+    check_arg(&branch, "branch")?;
+    ensure_no_operation(&repo_path)?;
+    let mut args = vec!["merge", "--no-edit"];
+    if no_ff {
+        args.push("--no-ff");
+    }
+    if let Some(ref m) = message {
+        args.push("-m");
+        args.push(m.as_str());
+    }
+    args.push(&branch);
+    let out = git_run(&repo_path, &args)?;
+    outcome_from_output(&repo_path, &out)
+}
+
+/// Rebase the current branch onto `upstream`.
+#[tauri::command]
+pub fn rebase_onto(repo_path: String, upstream: String) -> GitResult<MergeOutcome> {
+    check_arg(&upstream, "upstream")?;
+    ensure_no_operation(&repo_path)?;
+    let out = git_run(&repo_path, &["rebase", &upstream])?;
+    outcome_from_output(&repo_path, &out)
+}
+
+/// Current in-progress merge/rebase/cherry-pick/revert, if any.
+#[tauri::command]
+pub fn get_repo_state(repo_path: String) -> GitResult<Option<RepoState>> {
+    read_repo_state(&repo_path)
+}
+
+fn require_state(repo_path: &str) -> GitResult<RepoState> {
+    read_repo_state(repo_path)?.ok_or_else(|| {
+        GitError::new(
+            GitErrorKind::NotFound,
+            "No merge, rebase, cherry-pick or revert in progress",
+        )
+    })
+}
+
+/// Continue the in-progress operation after conflicts were resolved.
+/// Uses the prepared commit message without opening an editor.
+#[tauri::command]
+pub fn continue_repo_operation(repo_path: String) -> GitResult<MergeOutcome> {
+    let state = require_state(&repo_path)?;
+    if !state.conflicted_files.is_empty() {
+        return Err(GitError::new(
+            GitErrorKind::MergeConflict,
+            format!(
+                "Resolve {} conflicted file(s) before continuing",
+                state.conflicted_files.len()
+            ),
+        ));
+    }
+    let out = git_run_with_env(
+        &repo_path,
+        &[state.kind.command(), "--continue"],
+        &[("GIT_EDITOR", "true")],
+    )?;
+    outcome_from_output(&repo_path, &out)
+}
+
+/// Abort the in-progress operation and restore the pre-operation state.
+#[tauri::command]
+pub fn abort_repo_operation(repo_path: String) -> GitResult<()> {
+    let state = require_state(&repo_path)?;
+    let out = git_run(&repo_path, &[state.kind.command(), "--abort"])?;
+    if out.success {
+        Ok(())
+    } else {
+        Err(GitError::from_output(&out))
+    }
+}
+
+/// Skip the current commit of a rebase, cherry-pick or revert.
+#[tauri::command]
+pub fn skip_repo_operation(repo_path: String) -> GitResult<MergeOutcome> {
+    let state = require_state(&repo_path)?;
+    if state.kind == InProgressKind::Merge {
+        return Err("A merge can't be skipped; abort it instead".into());
+    }
+    let out = git_run_with_env(
+        &repo_path,
+        &[state.kind.command(), "--skip"],
+        &[("GIT_EDITOR", "true")],
+    )?;
+    outcome_from_output(&repo_path, &out)
+}
+
+/// Shared argument handling for cherry-pick and revert. `commits` may mix
+/// single revisions and `A..B` ranges.
+fn run_sequencer(
+    repo_path: &str,
+    command: &str,
+    commits: &[String],
+    extra: &[&str],
+    no_commit: bool,
+    mainline: Option<u32>,
+) -> GitResult<MergeOutcome> {
+    if commits.is_empty() {
+        return Err(format!("No commits to {}", command).into());
+    }
+    for commit in commits {
+        check_arg(commit, "commit")?;
+    }
+    ensure_no_operation(repo_path)?;
+
+    let mainline = mainline.map(|m| m.to_string());
+    let mut args = vec![command];
+    args.extend_from_slice(extra);
+    if no_commit {
+        args.push("--no-commit");
+    }
+    if let Some(ref m) = mainline {
+        args.push("-m");
+        args.push(m);
+    }
+    args.extend(commits.iter().map(String::as_str));
+
+    let out = git_run_with_env(repo_path, &args, &[("GIT_EDITOR", "true")])?;
+    outcome_from_output(repo_path, &out)
+}
+
+/// Cherry-pick commits or ranges onto the current branch. `record_origin`
+/// appends "(cherry picked from commit …)" (`-x`); `mainline` picks the
+/// parent to diff against when picking a merge commit.
+#[tauri::command]
+pub fn cherry_pick(
+    repo_path: String,
+    commits: Vec<String>,
+    record_origin: bool,
+    no_commit: bool,
+    mainline: Option<u32>,
+) -> GitResult<MergeOutcome> {
+    let extra: &[&str] = if record_origin { &["-x"] } else { &[] };
+    run_sequencer(
+        &repo_path,
+        "cherry-pick",
+        &commits,
+        extra,
+        no_commit,
+        mainline,
+    )
+}
+
+/// Revert commits or ranges with new commits (or only in the working tree
+/// with `no_commit`).
+#[tauri::command]
+pub fn revert_commits(
+    repo_path: String,
+    commits: Vec<String>,
+    no_commit: bool,
+    mainline: Option<u32>,
+) -> GitResult<MergeOutcome> {
+    run_sequencer(
+        &repo_path,
+        "revert",
+        &commits,
+        &["--no-edit"],
+        no_commit,
+        mainline,
+    )
+}
+
+fn show_stage(repo_path: &str, stage: u8, path: &str) -> GitResult<Option<String>> {
+    let spec = format!(":{}:{}", stage, path);
+    let out = git_run(repo_path, &["show", &spec])?;
+    Ok(if out.success { Some(out.stdout) } else { None })
+}
+
+/// List conflicted files with their base/ours/theirs versions.
+#[tauri::command]
+pub fn list_conflicts(repo_path: String) -> GitResult<Vec<ConflictFile>> {
+    let out = git_run(&repo_path, &["ls-files", "-u", "-z"])?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+
+    // Records: "<mode> <object> <stage>\t<path>"
+    let mut stages: Vec<(String, Vec<u8>)> = Vec::new();
+    for record in out.stdout.split('\0').filter(|r| !r.is_empty()) {
+        let Some((meta, path)) = record.split_once('\t') else {
+            continue;
+        };
+        let Some(stage) = meta
+            .split_whitespace()
+            .nth(2)
+            .and_then(|s| s.parse::<u8>().ok())
+        else {
+            continue;
+        };
+        match stages.iter_mut().find(|(p, _)| p == path) {
+            Some((_, s)) => s.push(stage),
+            None => stages.push((path.to_string(), vec![stage])),
+        }
+    }
+
+    let mut conflicts = Vec::with_capacity(stages.len());
+    for (path, present) in stages {
+        let has = |n: u8| present.contains(&n);
+        let conflict_type = match (has(1), has(2), has(3)) {
+            (true, true, true) => "both_modified",
+            (false, true, true) => "both_added",
+            (true, false, true) => "deleted_by_us",
+            (true, true, false) => "deleted_by_them",
+            (false, true, false) => "added_by_us",
+            // e.g. a file renamed differently on both sides
+            (true, false, false) => "both_deleted",
+            _ => "added_by_them",
+        };
+        let working = std::fs::read(Path::new(&repo_path).join(&path))
+            .ok()
+            .map(|b| String::from_utf8_lossy(&b).into_owned());
+        conflicts.push(ConflictFile {
+            conflict_type: conflict_type.to_string(),
+            base: if has(1) {
+                show_stage(&repo_path, 1, &path)?
+            } else {
+                None
+            },
+            ours: if has(2) {
+                show_stage(&repo_path, 2, &path)?
+            } else {
+                None
+            },
+            theirs: if has(3) {
+                show_stage(&repo_path, 3, &path)?
+            } else {
+                None
+            },
+            working,
+            path,
+        });
+    }
+    Ok(conflicts)
+}
+
+/// Resolve a conflicted file by taking one side entirely. If that side
+/// deleted the file, the deletion is staged.
+#[tauri::command]
+pub fn resolve_conflict_side(repo_path: String, path: String, side: ConflictSide) -> GitResult<()> {
+    let stage = match side {
+        ConflictSide::Ours => 2,
+        ConflictSide::Theirs => 3,
+    };
+    let out = if show_stage(&repo_path, stage, &path)?.is_some() {
+        let flag = match side {
+            ConflictSide::Ours => "--ours",
+            ConflictSide::Theirs => "--theirs",
+        };
+        let out = git_run(&repo_path, &["checkout", flag, "--", &path])?;
+        if !out.success {
+            return Err(GitError::from_output(&out));
+        }
+        git_run(&repo_path, &["add", "--", &path])?
+    } else {
+        git_run(&repo_path, &["rm", "--quiet", "--", &path])?
+    };
+    if out.success {
+        Ok(())
+    } else {
+        Err(GitError::from_output(&out))
+    }
+}
+
+/// Resolve a conflicted file with user-edited content and stage it.
+#[tauri::command]
+pub fn resolve_conflict_with_content(
+    repo_path: String,
+    path: String,
+    content: String,
+) -> GitResult<()> {
+    std::fs::write(Path::new(&repo_path).join(&path), content)?;
+    let out = git_run(&repo_path, &["add", "--", &path])?;
+    if out.success {
+        Ok(())
+    } else {
+        Err(GitError::from_output(&out))
+    }
+}
+
+/// Parse the conflict markers in the working-tree copy of `path`.
+#[tauri::command]
+pub fn get_conflict_blocks(repo_path: String, path: String) -> GitResult<Vec<ConflictBlock>> {
+    let content = std::fs::read_to_string(Path::new(&repo_path).join(&path))?;
+    Ok(parse_conflicts(&content))
+}
+
+/// Resolve individual conflict blocks of `path` and write the file back.
+/// Returns the blocks still left; once none remain the file is staged.
+#[tauri::command]
+pub fn resolve_conflict_blocks(
+    repo_path: String,
+    path: String,
+    resolutions: Vec<BlockResolution>,
+) -> GitResult<Vec<ConflictBlock>> {
+    let file = Path::new(&repo_path).join(&path);
+    let content = std::fs::read_to_string(&file)?;
+    let resolved = resolve_conflicts(&content, &resolutions)?;
+    std::fs::write(&file, &resolved)?;
+
+    let remaining = parse_conflicts(&resolved);
+    if remaining.is_empty() {
+        let out = git_run(&repo_path, &["add", "--", &path])?;
+        if !out.success {
+            return Err(GitError::from_output(&out));
+        }
+    }
+    Ok(remaining)
+}
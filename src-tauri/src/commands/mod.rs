@@ -1,9 +1,23 @@
 pub mod branch;
+pub mod changelog;
+pub mod commit_message;
 pub mod diff;
+pub mod error;
 pub mod git;
+pub mod graph;
+pub mod history;
+pub mod hooks;
+pub mod hunks;
+pub mod merge;
 pub mod oauth;
+pub mod operations;
+pub mod progress;
+pub mod rebase;
+pub mod release;
 pub mod remote;
 pub mod repo;
 pub mod stash;
+pub mod status;
 pub mod tags;
+pub mod watcher;
 pub mod worktree;
@@ -1,3 +1,4 @@
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
@@ -13,7 +14,7 @@ pub struct DeviceCodeData {
 /// Start the GitHub OAuth Device Flow.
 /// Returns device_code, user_code, verification_uri, etc.
 #[tauri::command]
-pub async fn github_start_device_flow(client_id: String) -> Result<DeviceCodeData, String> {
+pub async fn github_start_device_flow(client_id: String) -> GitResult<DeviceCodeData> {
     let client = Client::new();
     let res = client
         .post("https://github.com/login/device/code")
@@ -23,20 +24,19 @@ pub async fn github_start_device_flow(client_id: String) -> Result<DeviceCodeDat
             "scope": "read:user repo"
         }))
         .send()
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
-    let body: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
+    let body: serde_json::Value = res.json().await?;
 
     if let Some(err) = body.get("error").and_then(|v| v.as_str()) {
         let desc = body
             .get("error_description")
             .and_then(|v| v.as_str())
             .unwrap_or(err);
-        return Err(desc.to_string());
+        return Err(GitError::new(GitErrorKind::GitHub, desc));
     }
 
-    serde_json::from_value(body).map_err(|e| e.to_string())
+    serde_json::from_value(body).map_err(|e| GitError::new(GitErrorKind::GitHub, e.to_string()))
 }
 
 /// Poll GitHub for the access token during Device Flow.
@@ -46,7 +46,7 @@ pub async fn github_start_device_flow(client_id: String) -> Result<DeviceCodeDat
 pub async fn github_poll_device_token(
     client_id: String,
     device_code: String,
-) -> Result<Option<String>, String> {
+) -> GitResult<Option<String>> {
     let client = Client::new();
     let res = client
         .post("https://github.com/login/oauth/access_token")
@@ -57,10 +57,9 @@ pub async fn github_poll_device_token(
             "grant_type": "urn:ietf:params:oauth:grant-type:device_code"
         }))
         .send()
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
-    let body: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
+    let body: serde_json::Value = res.json().await?;
 
     if let Some(token) = body.get("access_token").and_then(|v| v.as_str()) {
         return Ok(Some(token.to_string()));
@@ -73,7 +72,7 @@ pub async fn github_poll_device_token(
                 .get("error_description")
                 .and_then(|v| v.as_str())
                 .unwrap_or(e);
-            Err(desc.to_string())
+            Err(GitError::new(GitErrorKind::GitHub, desc))
         }
         None => Ok(None),
     }
@@ -0,0 +1,125 @@
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// A git process that can be cancelled from the frontend.
+struct RunningOperation {
+    info: OperationInfo,
+    child: Arc<Mutex<Child>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationInfo {
+    pub id: u64,
+    /// "clone", "push", "pull", "fetch" or "commit"
+    pub operation: String,
+    pub repo_path: String,
+}
+
+/// Event emitted with an [`OperationInfo`] as soon as a cancellable git
+/// process has started, before it reports any progress.
+pub const OPERATION_STARTED_EVENT: &str = "git-operation-started";
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static OPERATIONS: OnceLock<Mutex<HashMap<u64, RunningOperation>>> = OnceLock::new();
+
+fn operations() -> &'static Mutex<HashMap<u64, RunningOperation>> {
+    OPERATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Registration of a spawned git process. Dropping it removes the
+/// operation from the registry.
+pub struct OperationHandle {
+    pub id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl OperationHandle {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for OperationHandle {
+    fn drop(&mut self) {
+        if let Ok(mut ops) = operations().lock() {
+            ops.remove(&self.id);
+        }
+    }
+}
+
+/// Track `child` under a fresh operation ID so it can be cancelled.
+pub fn register(operation: &str, repo_path: &str, child: Arc<Mutex<Child>>) -> OperationHandle {
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    let cancelled = Arc::new(AtomicBool::new(false));
+    if let Ok(mut ops) = operations().lock() {
+        ops.insert(
+            id,
+            RunningOperation {
+                info: OperationInfo {
+                    id,
+                    operation: operation.to_string(),
+                    repo_path: repo_path.to_string(),
+                },
+                child,
+                cancelled: cancelled.clone(),
+            },
+        );
+    }
+    OperationHandle { id, cancelled }
+}
+
+/// Error returned by an operation that was stopped via `cancel_operation`.
+pub fn cancelled_error(operation: &str) -> GitError {
+    GitError::new(
+        GitErrorKind::Cancelled,
+        format!("git {} was cancelled", operation),
+    )
+}
+
+/// List the long-running git operations currently in flight.
+#[tauri::command]
+pub fn list_operations() -> GitResult<Vec<OperationInfo>> {
+    let ops = operations()
+        .lock()
+        .map_err(|_| GitError::from("Operation registry is unavailable"))?;
+    let mut list: Vec<OperationInfo> = ops.values().map(|op| op.info.clone()).collect();
+    list.sort_by_key(|op| op.id);
+    Ok(list)
+}
+
+/// Kill the git process behind `id`. The command that started it then
+/// fails with a `cancelled` error after cleaning up. An operation that has
+/// already exited is left alone.
+#[tauri::command]
+pub fn cancel_operation(id: u64) -> GitResult<()> {
+    let ops = operations()
+        .lock()
+        .map_err(|_| GitError::from("Operation registry is unavailable"))?;
+    let Some(op) = ops.get(&id) else {
+        return Err(GitError::new(
+            GitErrorKind::NotFound,
+            format!("No running operation with id {}", id),
+        ));
+    };
+
+    let mut child = match op.child.lock() {
+        Ok(child) => child,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    // A process that already exited finishes normally: killing it would
+    // still succeed, and flagging it would throw away a good result.
+    if child.try_wait()?.is_some() {
+        return Ok(());
+    }
+    // Set the flag while holding the lock, so the poll loop in
+    // `git_run_streaming` can't see the exit without it.
+    if child.kill().is_ok() {
+        op.cancelled.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
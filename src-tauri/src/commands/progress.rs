@@ -0,0 +1,262 @@
+use crate::commands::error::GitResult;
+use crate::commands::git::{git_command, spawn_error, GitOutput};
+use crate::commands::operations::{
+    cancelled_error, register, OperationInfo, OPERATION_STARTED_EVENT,
+};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Read};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::Emitter;
+
+/// Event emitted for every progress update of a long-running git operation.
+pub const PROGRESS_EVENT: &str = "git-progress";
+
+/// One parsed progress line from git's stderr, e.g.
+/// `Receiving objects:  42% (420/1000), 1.20 MiB | 2.40 MiB/s`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitProgress {
+    /// ID to pass to `cancel_operation`
+    pub operation_id: u64,
+    /// "clone", "push", "pull", "fetch" or "commit"
+    pub operation: String,
+    /// e.g. "Counting objects", "Receiving objects", "Resolving deltas";
+    /// empty for informational lines
+    pub phase: String,
+    pub percent: Option<u8>,
+    pub current: Option<u64>,
+    pub total: Option<u64>,
+    /// Transfer rate as printed by git, e.g. "2.40 MiB/s"
+    pub throughput: Option<String>,
+    /// The raw line (without the `remote: ` prefix)
+    pub line: String,
+}
+
+impl GitProgress {
+    /// True for counter lines, false for informational messages.
+    pub fn is_counter(&self) -> bool {
+        !self.phase.is_empty() && (self.percent.is_some() || self.current.is_some())
+    }
+}
+
+/// Parse a single line of git `--progress` output.
+pub fn parse_progress_line(operation: &str, raw: &str) -> GitProgress {
+    let line = raw.trim();
+    let line = line.strip_prefix("remote:").map(str::trim).unwrap_or(line);
+
+    let mut progress = GitProgress {
+        operation_id: 0,
+        operation: operation.to_string(),
+        phase: String::new(),
+        percent: None,
+        current: None,
+        total: None,
+        throughput: None,
+        line: line.to_string(),
+    };
+
+    let Some((phase, rest)) = line.split_once(':') else {
+        return progress;
+    };
+    let rest = rest.trim();
+    // Counter lines always continue with a number ("45% (9/20)" or "5, done.")
+    if !rest.starts_with(|c: char| c.is_ascii_digit()) {
+        return progress;
+    }
+    progress.phase = phase.trim().to_string();
+
+    let first = rest
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .next()
+        .unwrap_or("");
+    if let Some(pct) = first.strip_suffix('%') {
+        progress.percent = pct.parse().ok();
+        // "(current/total)"
+        if let Some(counts) = rest
+            .split_once('(')
+            .and_then(|(_, r)| r.split_once(')'))
+            .map(|(c, _)| c)
+        {
+            if let Some((cur, tot)) = counts.split_once('/') {
+                progress.current = cur.trim().parse().ok();
+                progress.total = tot.trim().parse().ok();
+            }
+        }
+    } else {
+        progress.current = first.parse().ok();
+    }
+
+    // ", 1.20 MiB | 2.40 MiB/s"
+    if let Some((_, rate)) = rest.rsplit_once('|') {
+        let rate = rate.trim().trim_end_matches(", done.").trim();
+        if rate.ends_with("/s") {
+            progress.throughput = Some(rate.to_string());
+        }
+    }
+
+    progress
+}
+
+/// Run a blocking git call on tokio's blocking pool so a long push or
+/// clone doesn't tie up an async worker.
+pub async fn run_blocking<T, F>(f: F) -> GitResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> GitResult<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| format!("failed to join git task: {e}"))?
+}
+
+/// Whether the process was ended by a kill rather than exiting by itself.
+#[cfg(unix)]
+fn killed(status: &std::process::ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal().is_some()
+}
+
+/// Whether the process was ended by a kill rather than exiting by itself.
+/// `TerminateProcess` leaves exit code 1, which git also uses for failures.
+#[cfg(not(unix))]
+fn killed(status: &std::process::ExitStatus) -> bool {
+    !status.success()
+}
+
+/// Run a long-running git command, streaming every progress update as a
+/// [`PROGRESS_EVENT`]. Callers should pass `--progress` since stderr is not
+/// a terminal.
+///
+/// The process is registered as a cancellable operation for its lifetime
+/// and announced with an `OPERATION_STARTED_EVENT`; if it is killed through
+/// `cancel_operation` this returns a `cancelled` error instead of the output.
+///
+/// This blocks until git exits; async commands should call it through
+/// [`run_blocking`].
+///
+/// The returned stderr only contains the non-counter lines, so it can still
+/// be shown as a summary or classified on failure.
+pub fn git_run_streaming(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    args: &[&str],
+    operation: &str,
+) -> GitResult<GitOutput> {
+    let mut child = git_command(repo_path)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_error)?;
+
+    let mut stdout = child.stdout.take().unwrap();
+    let stderr = child.stderr.take().unwrap();
+    let child = Arc::new(Mutex::new(child));
+    let handle = register(operation, repo_path, child.clone());
+    let operation_id = handle.id;
+    let _ = app.emit(
+        OPERATION_STARTED_EVENT,
+        OperationInfo {
+            id: operation_id,
+            operation: operation.to_string(),
+            repo_path: repo_path.to_string(),
+        },
+    );
+
+    let stdout_handle = std::thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stdout.read_to_string(&mut buf);
+        buf
+    });
+
+    // Read stderr in a dedicated thread so the pipe never blocks.
+    // git writes progress using \r to overwrite lines, and \n at the end of
+    // each logical section. We split on both to capture every update.
+    let app_clone = app.clone();
+    let operation_name = operation.to_string();
+    let stderr_handle = std::thread::spawn(move || -> String {
+        let reader = std::io::BufReader::new(stderr);
+        let mut summary = String::new();
+        for chunk in reader.split(b'\n') {
+            let Ok(bytes) = chunk else {
+                break;
+            };
+            let text = String::from_utf8_lossy(&bytes);
+            for part in text.split('\r') {
+                if part.trim().is_empty() {
+                    continue;
+                }
+                let mut progress = parse_progress_line(&operation_name, part);
+                progress.operation_id = operation_id;
+                if !progress.is_counter() {
+                    summary.push_str(part.trim_end());
+                    summary.push('\n');
+                }
+                let _ = app_clone.emit(PROGRESS_EVENT, progress);
+            }
+        }
+        summary
+    });
+
+    // Poll instead of blocking in wait() so cancel_operation can take the
+    // lock and kill the child in the meantime.
+    let status = loop {
+        let exited = match child.lock() {
+            Ok(mut c) => c.try_wait()?,
+            Err(poisoned) => poisoned.into_inner().try_wait()?,
+        };
+        if let Some(status) = exited {
+            break status;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    if handle.is_cancelled() && killed(&status) {
+        // Don't join the readers: helpers git spawned (e.g. git-remote-https)
+        // may keep the pipes open for a while after the kill.
+        return Err(cancelled_error(operation));
+    }
+
+    let stdout = stdout_handle.join().unwrap_or_default();
+    let stderr = stderr_handle.join().unwrap_or_default();
+
+    Ok(GitOutput {
+        stdout,
+        stderr,
+        success: status.success(),
+        code: status.code().unwrap_or(-1),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_receiving_objects() {
+        let p = parse_progress_line(
+            "clone",
+            "Receiving objects:  42% (420/1000), 1.20 MiB | 2.40 MiB/s",
+        );
+        assert_eq!(p.phase, "Receiving objects");
+        assert_eq!(p.percent, Some(42));
+        assert_eq!((p.current, p.total), (Some(420), Some(1000)));
+        assert_eq!(p.throughput.as_deref(), Some("2.40 MiB/s"));
+        assert!(p.is_counter());
+    }
+
+    #[test]
+    fn test_parse_remote_and_plain_lines() {
+        let p = parse_progress_line("fetch", "remote: Enumerating objects: 5, done.");
+        assert_eq!(p.phase, "Enumerating objects");
+        assert_eq!(p.current, Some(5));
+        assert_eq!(p.percent, None);
+
+        let p = parse_progress_line("push", "To github.com:owner/repo.git");
+        assert!(p.phase.is_empty());
+        assert!(!p.is_counter());
+        assert_eq!(p.line, "To github.com:owner/repo.git");
+    }
+}
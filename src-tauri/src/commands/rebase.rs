@@ -0,0 +1,263 @@
+use crate::commands::diff::{parse_log_lines, CommitInfo};
+use crate::commands::error::{GitError, GitResult};
+use crate::commands::git::{git_run, git_run_with_env, sh_quote};
+use crate::commands::merge::{ensure_no_operation, git_dir, outcome_from_output, MergeOutcome};
+use crate::commands::remote::check_arg;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RebaseAction {
+    Pick,
+    Reword,
+    Squash,
+    Fixup,
+    Drop,
+    Edit,
+}
+
+/// One line of an interactive rebase plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebaseTodoItem {
+    pub action: RebaseAction,
+    pub commit: CommitInfo,
+    /// New message for `reword`, or for the combined commit when set on a
+    /// `squash`/`fixup` (the last one in a chain wins). Ignored otherwise.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Directory inside the git dir holding the plan and reworded messages
+/// until git starts the rebase. The sequence editor then moves the messages
+/// into git's own `rebase-merge` directory, which git removes however the
+/// rebase ends.
+const PLAN_DIR: &str = "easygit-rebase";
+
+fn is_squash(action: RebaseAction) -> bool {
+    matches!(action, RebaseAction::Squash | RebaseAction::Fixup)
+}
+
+/// Render `plan` as a git-rebase-todo. Messages are applied with
+/// `exec git commit --amend -F <file>` after the commit (or the end of its
+/// squash chain), so git never needs an editor. Returns the todo text and
+/// the message files to write as `(file name, message)`.
+pub fn build_todo(
+    plan: &[RebaseTodoItem],
+    message_dir: &str,
+) -> GitResult<(String, Vec<(String, String)>)> {
+    let mut todo = String::new();
+    let mut messages = Vec::new();
+    let mut pending: Option<&str> = None;
+    let mut has_target = false;
+
+    for (i, item) in plan.iter().enumerate() {
+        let hash = item.commit.hash.trim();
+        if hash.is_empty() || hash.contains(char::is_whitespace) {
+            return Err(
+                format!("Invalid commit hash {:?} in rebase plan", item.commit.hash).into(),
+            );
+        }
+        if is_squash(item.action) && !has_target {
+            return Err(format!(
+                "Cannot {} {} without a previous commit",
+                if item.action == RebaseAction::Squash {
+                    "squash"
+                } else {
+                    "fixup"
+                },
+                item.commit.short_hash
+            )
+            .into());
+        }
+
+        let action = match item.action {
+            RebaseAction::Pick | RebaseAction::Reword => "pick",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+            RebaseAction::Edit => "edit",
+        };
+        todo.push_str(&format!("{} {} {}\n", action, hash, item.commit.message));
+
+        match item.action {
+            RebaseAction::Drop => continue,
+            RebaseAction::Reword if item.message.is_none() => {
+                return Err(
+                    format!("No new message for reword of {}", item.commit.short_hash).into(),
+                )
+            }
+            RebaseAction::Pick
+            | RebaseAction::Reword
+            | RebaseAction::Squash
+            | RebaseAction::Fixup => {
+                if let Some(ref message) = item.message {
+                    pending = Some(message);
+                }
+            }
+            RebaseAction::Edit => pending = None,
+        }
+        has_target = true;
+
+        let chain_continues = plan[i + 1..]
+            .iter()
+            .find(|next| next.action != RebaseAction::Drop)
+            .is_some_and(|next| is_squash(next.action));
+        if !chain_continues {
+            if let Some(message) = pending.take() {
+                let name = format!("easygit-msg-{}", messages.len());
+                let path = format!("{}/{}", message_dir, name);
+                todo.push_str(&format!(
+                    "exec git commit --amend --quiet -F {}\n",
+                    sh_quote(&path)
+                ));
+                messages.push((name, message.to_string()));
+            }
+        }
+    }
+
+    if todo.is_empty() {
+        return Err("Rebase plan is empty".into());
+    }
+    Ok((todo, messages))
+}
+
+/// Commits between `base` and HEAD, oldest first, as an all-`pick` plan.
+#[tauri::command]
+pub fn get_rebase_plan(repo_path: String, base: String) -> GitResult<Vec<RebaseTodoItem>> {
+    check_arg(&base, "base")?;
+    let range = format!("{}..HEAD", base);
+    let out = git_run(
+        &repo_path,
+        &[
+            "log",
+            "--reverse",
+            "--no-merges",
+            "--format=%H|%h|%an|%ai|%s",
+            &range,
+        ],
+    )?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    Ok(parse_log_lines(&out.stdout)
+        .into_iter()
+        .map(|commit| RebaseTodoItem {
+            action: RebaseAction::Pick,
+            commit,
+            message: None,
+        })
+        .collect())
+}
+
+/// Run `git rebase -i <base>` with `plan` as the todo list, without ever
+/// opening an editor. Stops for conflicts or `edit` are reported in the
+/// outcome; continue with `continue_repo_operation`.
+#[tauri::command]
+pub fn interactive_rebase(
+    repo_path: String,
+    base: String,
+    plan: Vec<RebaseTodoItem>,
+) -> GitResult<MergeOutcome> {
+    check_arg(&base, "base")?;
+    ensure_no_operation(&repo_path)?;
+    let git_dir = git_dir(&repo_path)?;
+    let dir = git_dir.join(PLAN_DIR);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    // Forward slashes work for git's sh on every platform
+    let dir_str = dir.to_string_lossy().replace('\\', "/");
+    let state_dir = git_dir
+        .join("rebase-merge")
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    let (todo, messages) = build_todo(&plan, &state_dir)?;
+    for (name, message) in &messages {
+        std::fs::write(dir.join(name), message)?;
+    }
+    std::fs::write(dir.join("todo"), todo)?;
+
+    // Runs once git has created rebase-merge; "$1" is its todo file
+    let mut script = format!("cp {} \"$1\"", sh_quote(&format!("{}/todo", dir_str)));
+    for (name, _) in &messages {
+        script.push_str(&format!(
+            " &&\nmv {} \"$(dirname \"$1\")\"/",
+            sh_quote(&format!("{}/{}", dir_str, name))
+        ));
+    }
+    std::fs::write(dir.join("sequence-editor"), script + "\n")?;
+    let sequence_editor = format!("sh {}", sh_quote(&format!("{}/sequence-editor", dir_str)));
+    let out = git_run_with_env(
+        &repo_path,
+        &["rebase", "-i", "--no-autosquash", &base],
+        &[
+            ("GIT_SEQUENCE_EDITOR", sequence_editor.as_str()),
+            ("GIT_EDITOR", "true"),
+        ],
+    )?;
+    let _ = std::fs::remove_dir_all(&dir);
+    outcome_from_output(&repo_path, &out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(action: RebaseAction, hash: &str, message: Option<&str>) -> RebaseTodoItem {
+        RebaseTodoItem {
+            action,
+            commit: CommitInfo {
+                hash: hash.to_string(),
+                short_hash: hash.to_string(),
+                author: "a".to_string(),
+                date: "2024-01-01".to_string(),
+                message: format!("subject {}", hash),
+            },
+            message: message.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_build_todo_rewords_after_squash_chain() {
+        let plan = [
+            item(RebaseAction::Reword, "aaa", Some("first")),
+            item(RebaseAction::Pick, "bbb", None),
+            item(RebaseAction::Squash, "ccc", None),
+            item(RebaseAction::Drop, "ddd", None),
+            item(RebaseAction::Fixup, "eee", Some("combined")),
+            item(RebaseAction::Edit, "fff", None),
+        ];
+        let (todo, messages) = build_todo(&plan, "/r/.git/rebase-merge").unwrap();
+        let exec = "exec git commit --amend --quiet -F";
+        assert_eq!(
+            todo,
+            format!(
+                "pick aaa subject aaa\n\
+{exec} '/r/.git/rebase-merge/easygit-msg-0'\n\
+pick bbb subject bbb\n\
+squash ccc subject ccc\n\
+drop ddd subject ddd\n\
+fixup eee subject eee\n\
+{exec} '/r/.git/rebase-merge/easygit-msg-1'\n\
+edit fff subject fff\n"
+            )
+        );
+        assert_eq!(
+            messages[0],
+            ("easygit-msg-0".to_string(), "first".to_string())
+        );
+        assert_eq!(
+            messages[1],
+            ("easygit-msg-1".to_string(), "combined".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_todo_rejects_leading_squash() {
+        let plan = [
+            item(RebaseAction::Drop, "aaa", None),
+            item(RebaseAction::Fixup, "bbb", None),
+        ];
+        assert!(build_todo(&plan, "/tmp").is_err());
+    }
+}
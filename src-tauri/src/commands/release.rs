@@ -0,0 +1,448 @@
+use crate::commands::commit_message::parse_commit_message;
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
+use crate::commands::git::git_run;
+use crate::commands::tags::list_tags;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Changelog sections in display order, keyed by conventional-commit type.
+/// Commits of any other type (or not conventional at all) go under "Other".
+const SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("revert", "Reverts"),
+    ("refactor", "Refactoring"),
+    ("docs", "Documentation"),
+    ("test", "Tests"),
+    ("ci", "CI"),
+    ("style", "Styles"),
+    ("chore", "Chores"),
+];
+
+/// A `major.minor.patch[-pre]` version parsed from a tag name. Whatever
+/// precedes the first digit ("v", "release-") is kept as `prefix`; build
+/// metadata is dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Version {
+    pub prefix: String,
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+}
+
+impl Version {
+    pub fn parse(tag: &str) -> Option<Self> {
+        let start = tag.find(|c: char| c.is_ascii_digit())?;
+        let (prefix, rest) = tag.split_at(start);
+        let rest = rest.split('+').next().unwrap_or(rest);
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre.to_string())),
+            Some(_) => return None,
+            None => (rest, None),
+        };
+        let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+        let (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+        Some(Self {
+            prefix: prefix.to_string(),
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    /// Bump level a prerelease is heading for: `2.0.0-rc.1` is a major.
+    fn pre_level(&self) -> BumpLevel {
+        if self.minor == 0 && self.patch == 0 {
+            BumpLevel::Major
+        } else if self.patch == 0 {
+            BumpLevel::Minor
+        } else {
+            BumpLevel::Patch
+        }
+    }
+
+    /// Next version for `level`, as a prerelease with identifier `pre_id`
+    /// when given. A prerelease already heading for at least `level` keeps
+    /// its version and is either released or gets its counter increased.
+    pub fn bump(&self, level: BumpLevel, pre_id: Option<&str>) -> Self {
+        let mut next = Self {
+            pre: None,
+            ..self.clone()
+        };
+        let within_pre = self.pre.is_some() && level <= self.pre_level();
+        if !within_pre {
+            match level {
+                BumpLevel::Major => {
+                    next.major += 1;
+                    next.minor = 0;
+                    next.patch = 0;
+                }
+                BumpLevel::Minor => {
+                    next.minor += 1;
+                    next.patch = 0;
+                }
+                BumpLevel::Patch => next.patch += 1,
+                BumpLevel::None => return self.clone(),
+            }
+        }
+
+        if let Some(id) = pre_id.map(str::trim).filter(|id| !id.is_empty()) {
+            let counter = match self.pre.as_deref().and_then(|p| p.rsplit_once('.')) {
+                Some((current, n)) if within_pre && current == id => {
+                    n.parse::<u64>().map_or(1, |n| n + 1)
+                }
+                _ => 1,
+            };
+            next.pre = Some(format!("{}.{}", id, counter));
+        }
+        next
+    }
+
+    /// Semver precedence, ignoring the prefix.
+    fn precedence(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_pre(a, b),
+            })
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}.{}.{}",
+            self.prefix, self.major, self.minor, self.patch
+        )?;
+        if let Some(ref pre) = self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+/// Dot-separated identifiers; numeric ones compare numerically and sort
+/// before alphanumeric ones.
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    let mut a_ids = a.split('.');
+    let mut b_ids = b.split('.');
+    loop {
+        match (a_ids.next(), b_ids.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let order = match (x.parse::<u64>(), y.parse::<u64>()) {
+                    (Ok(x), Ok(y)) => x.cmp(&y),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => x.cmp(y),
+                };
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpLevel {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+/// A commit in a release, parsed as a conventional commit where possible.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub date: String,
+    /// Conventional-commit type, or None for free-form messages
+    #[serde(rename = "type")]
+    pub commit_type: Option<String>,
+    pub scope: Option<String>,
+    /// Description, or the whole subject for free-form messages
+    pub description: String,
+    pub breaking: bool,
+    /// Text of the `BREAKING CHANGE` footer, if any
+    pub breaking_note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogSection {
+    /// Commit type the section collects, "breaking" or "other"
+    pub key: String,
+    pub title: String,
+    pub commits: Vec<ReleaseCommit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionSuggestion {
+    /// Highest tag that parses as a version; None when there is none
+    pub current_tag: Option<String>,
+    pub current_version: Option<Version>,
+    pub bump: BumpLevel,
+    pub next_version: Version,
+    /// `next_version` rendered with the current tag's prefix
+    pub next_tag: String,
+    pub commit_count: usize,
+    pub sections: Vec<ChangelogSection>,
+}
+
+impl ReleaseCommit {
+    fn level(&self) -> BumpLevel {
+        match self.commit_type.as_deref() {
+            _ if self.breaking => BumpLevel::Major,
+            Some("feat") => BumpLevel::Minor,
+            _ => BumpLevel::Patch,
+        }
+    }
+}
+
+/// Parse `git log --format=%H%x1f%h%x1f%an%x1f%as%x1f%B%x1e` output.
+fn parse_release_commits(stdout: &str) -> Vec<ReleaseCommit> {
+    stdout
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(5, '\x1f');
+            let hash = fields.next()?.trim().to_string();
+            let short_hash = fields.next()?.to_string();
+            let author = fields.next()?.to_string();
+            let date = fields.next()?.to_string();
+            let message = fields.next()?.trim();
+            if hash.is_empty() {
+                return None;
+            }
+            Some(match parse_commit_message(message) {
+                Some(parsed) => ReleaseCommit {
+                    hash,
+                    short_hash,
+                    author,
+                    date,
+                    breaking_note: parsed
+                        .footers
+                        .iter()
+                        .find(|f| f.token.starts_with("BREAKING"))
+                        .map(|f| f.value.clone()),
+                    commit_type: Some(parsed.commit_type.to_lowercase()),
+                    scope: parsed.scope,
+                    description: parsed.description,
+                    breaking: parsed.breaking,
+                },
+                None => ReleaseCommit {
+                    hash,
+                    short_hash,
+                    author,
+                    date,
+                    commit_type: None,
+                    scope: None,
+                    description: message.lines().next().unwrap_or("").to_string(),
+                    breaking: false,
+                    breaking_note: None,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Non-merge commits in `from..to` (everything reachable from `to` when
+/// `from` is None), newest first.
+pub fn release_commits(
+    repo_path: &str,
+    from: Option<&str>,
+    to: &str,
+) -> GitResult<Vec<ReleaseCommit>> {
+    let range = match from {
+        Some(from) => format!("{}..{}", from, to),
+        None => to.to_string(),
+    };
+    if range.starts_with('-') {
+        return Err(format!("Invalid revision range {:?}", range).into());
+    }
+    let out = git_run(
+        repo_path,
+        &[
+            "log",
+            "--no-merges",
+            "--format=%H%x1f%h%x1f%an%x1f%as%x1f%B%x1e",
+            &range,
+            "--",
+        ],
+    )?;
+    if !out.success {
+        let err = GitError::from_output(&out);
+        if err.kind == GitErrorKind::EmptyRepository {
+            return Ok(Vec::new());
+        }
+        return Err(err);
+    }
+    Ok(parse_release_commits(&out.stdout))
+}
+
+/// Group commits into changelog sections: breaking changes first, then one
+/// section per type in `SECTIONS` order, then everything else. Breaking
+/// commits also appear under their type. Empty sections are omitted.
+pub fn group_sections(commits: &[ReleaseCommit]) -> Vec<ChangelogSection> {
+    let section =
+        |key: &str, title: &str, filter: &dyn Fn(&ReleaseCommit) -> bool| ChangelogSection {
+            key: key.to_string(),
+            title: title.to_string(),
+            commits: commits.iter().filter(|c| filter(c)).cloned().collect(),
+        };
+    let is_known = |c: &ReleaseCommit| {
+        c.commit_type
+            .as_deref()
+            .is_some_and(|t| SECTIONS.iter().any(|(key, _)| *key == t))
+    };
+
+    let mut sections = vec![section("breaking", "Breaking Changes", &|c| c.breaking)];
+    for (key, title) in SECTIONS {
+        sections.push(section(key, title, &|c| {
+            c.commit_type.as_deref() == Some(*key)
+        }));
+    }
+    sections.push(section("other", "Other", &|c| !is_known(c)));
+    sections.retain(|s| !s.commits.is_empty());
+    sections
+}
+
+/// Suggest the next version from the conventional commits since the highest
+/// version tag: breaking changes bump major, `feat` minor, anything else
+/// patch. With `prerelease` (e.g. "rc") the result is a prerelease with
+/// that identifier. Without any version tag the suggestion is `v0.1.0`.
+#[tauri::command]
+pub fn suggest_next_version(
+    repo_path: String,
+    prerelease: Option<String>,
+) -> GitResult<VersionSuggestion> {
+    // Highest version rather than newest tag, so a hotfix tagged on an old
+    // line doesn't win; ties go to the newest (list_tags sorts newest first)
+    let current = list_tags(repo_path.clone())?
+        .into_iter()
+        .filter_map(|tag| Version::parse(&tag.name).map(|v| (tag.name, v)))
+        .reduce(|best, tag| {
+            if tag.1.precedence(&best.1) == Ordering::Greater {
+                tag
+            } else {
+                best
+            }
+        });
+
+    let commits = release_commits(
+        &repo_path,
+        current.as_ref().map(|(t, _)| t.as_str()),
+        "HEAD",
+    )?;
+    let bump = commits
+        .iter()
+        .map(ReleaseCommit::level)
+        .max()
+        .unwrap_or(BumpLevel::None);
+
+    let next_version = match current {
+        Some((_, ref version)) => version.bump(bump, prerelease.as_deref()),
+        None => Version {
+            prefix: "v".to_string(),
+            major: 0,
+            minor: 0,
+            patch: 0,
+            pre: None,
+        }
+        .bump(BumpLevel::Minor, prerelease.as_deref()),
+    };
+
+    Ok(VersionSuggestion {
+        next_tag: next_version.to_string(),
+        current_tag: current.as_ref().map(|(t, _)| t.clone()),
+        current_version: current.map(|(_, v)| v),
+        bump,
+        next_version,
+        commit_count: commits.len(),
+        sections: group_sections(&commits),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(tag: &str, level: BumpLevel, pre: Option<&str>) -> String {
+        Version::parse(tag).unwrap().bump(level, pre).to_string()
+    }
+
+    #[test]
+    fn test_version_bumps() {
+        assert_eq!(next("v1.2.3", BumpLevel::Patch, None), "v1.2.4");
+        assert_eq!(next("v1.2.3", BumpLevel::Minor, None), "v1.3.0");
+        assert_eq!(next("1.2.3+build.7", BumpLevel::Major, None), "2.0.0");
+        assert_eq!(next("v1.2.3", BumpLevel::Minor, Some("rc")), "v1.3.0-rc.1");
+        assert_eq!(
+            next("v1.3.0-rc.1", BumpLevel::Patch, Some("rc")),
+            "v1.3.0-rc.2"
+        );
+        assert_eq!(next("v1.3.0-rc.2", BumpLevel::Minor, None), "v1.3.0");
+        assert_eq!(
+            next("v1.3.0-rc.2", BumpLevel::Major, Some("rc")),
+            "v2.0.0-rc.1"
+        );
+        assert_eq!(
+            next("v1.3.0-beta.4", BumpLevel::Minor, Some("rc")),
+            "v1.3.0-rc.1"
+        );
+        assert!(Version::parse("nightly").is_none());
+        assert!(Version::parse("v1.2").is_none());
+
+        let rc = Version::parse("1.0.0-rc.10").unwrap();
+        assert_eq!(
+            rc.precedence(&Version::parse("1.0.0-rc.9").unwrap()),
+            Ordering::Greater
+        );
+        assert_eq!(
+            rc.precedence(&Version::parse("1.0.0").unwrap()),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_parse_and_group_commits() {
+        let log = concat!(
+            "a1\x1fa\x1fAnn\x1f2024-05-01\x1ffeat(ui): dark mode\n\x1e\n",
+            "b2\x1fb\x1fBob\x1f2024-05-02\x1ffix!: drop legacy flag\n",
+            "\nBREAKING CHANGE: --legacy is gone\n\x1e\n",
+            "c3\x1fc\x1fCy\x1f2024-05-03\x1fUpdate readme\n\x1e\n",
+        );
+        let commits = parse_release_commits(log);
+        assert_eq!(commits.len(), 3);
+        assert_eq!(
+            commits[1].breaking_note.as_deref(),
+            Some("--legacy is gone")
+        );
+        assert_eq!(commits[2].description, "Update readme");
+        assert_eq!(
+            commits.iter().map(ReleaseCommit::level).max(),
+            Some(BumpLevel::Major)
+        );
+
+        let keys: Vec<String> = group_sections(&commits)
+            .into_iter()
+            .map(|s| s.key)
+            .collect();
+        assert_eq!(keys, ["breaking", "feat", "fix", "other"]);
+    }
+}
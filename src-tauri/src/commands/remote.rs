@@ -1,36 +1,192 @@
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
 use crate::commands::git::git_run;
+use crate::commands::hooks::git_run_with_hooks;
+use crate::commands::progress::{git_run_streaming, run_blocking};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RemoteInfo {
     pub name: String,
+    /// Fetch URL
     pub url: String,
+    /// Push URL; same as `url` unless `remote.<name>.pushurl` is set
+    pub push_url: String,
+    /// `remote.<name>.prune`; None falls back to `fetch.prune`
+    pub prune: Option<bool>,
+}
+
+/// Remote names and refspecs come from the UI; keep them from being read
+/// as options.
+pub(crate) fn check_arg(value: &str, what: &str) -> GitResult<()> {
+    if value.trim().is_empty() || value.starts_with('-') {
+        return Err(format!("Invalid {} {:?}", what, value).into());
+    }
+    Ok(())
+}
+
+fn parse_remotes(remote_v: &str, config: &str) -> Vec<RemoteInfo> {
+    let mut remotes: Vec<RemoteInfo> = Vec::new();
+    for line in remote_v.lines() {
+        let Some((name, rest)) = line.split_once('\t') else {
+            continue;
+        };
+        // rest = "<url> (fetch)" or "<url> (push)"
+        let (url, kind) = rest.rsplit_once(' ').unwrap_or((rest, ""));
+        let index = match remotes.iter().position(|r| r.name == name) {
+            Some(i) => i,
+            None => {
+                remotes.push(RemoteInfo {
+                    name: name.to_string(),
+                    url: String::new(),
+                    push_url: String::new(),
+                    prune: None,
+                });
+                remotes.len() - 1
+            }
+        };
+        let remote = &mut remotes[index];
+        if kind == "(push)" {
+            remote.push_url = url.to_string();
+        } else {
+            remote.url = url.to_string();
+        }
+    }
+
+    // `remote.<name>.prune <bool>` lines from `git config --get-regexp`
+    for line in config.lines() {
+        let Some((key, value)) = line.split_once(' ') else {
+            continue;
+        };
+        let Some(name) = key
+            .strip_prefix("remote.")
+            .and_then(|k| k.strip_suffix(".prune"))
+        else {
+            continue;
+        };
+        if let Some(remote) = remotes.iter_mut().find(|r| r.name == name) {
+            remote.prune = Some(value.trim() == "true");
+        }
+    }
+    remotes
 }
 
 /// List all configured git remotes for the repo.
 #[tauri::command]
-pub fn get_remotes(repo_path: String) -> Result<Vec<RemoteInfo>, String> {
+pub fn get_remotes(repo_path: String) -> GitResult<Vec<RemoteInfo>> {
     let out = git_run(&repo_path, &["remote", "-v"])?;
-    // `git remote -v` prints two lines per remote (fetch + push); deduplicate by name.
-    let mut seen = std::collections::HashSet::new();
-    let remotes = out
-        .stdout
-        .lines()
-        .filter_map(|line| {
-            let mut parts = line.splitn(2, '\t');
-            let name = parts.next()?.trim().to_string();
-            let rest = parts.next().unwrap_or("");
-            // rest = "<url> (fetch)" or "<url> (push)"
-            let url = rest.split_whitespace().next().unwrap_or("").to_string();
-            if seen.insert(name.clone()) {
-                Some(RemoteInfo { name, url })
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    // Exits 1 when nothing matches
+    let config = git_run(
+        &repo_path,
+        &["config", "--bool", "--get-regexp", r"^remote\..*\.prune$"],
+    )?;
+    Ok(parse_remotes(&out.stdout, &config.stdout))
+}
+
+fn run_remote(repo_path: &str, args: &[&str]) -> GitResult<()> {
+    let out = git_run(repo_path, args)?;
+    if out.success {
+        Ok(())
+    } else {
+        Err(GitError::from_output(&out))
+    }
+}
+
+/// Add a remote. `push_url` sets a separate push URL.
+#[tauri::command]
+pub fn add_remote(
+    repo_path: String,
+    name: String,
+    url: String,
+    push_url: Option<String>,
+) -> GitResult<()> {
+    check_arg(&name, "remote name")?;
+    run_remote(&repo_path, &["remote", "add", "--", &name, &url])?;
+    if let Some(push_url) = push_url.filter(|u| !u.trim().is_empty()) {
+        run_remote(
+            &repo_path,
+            &["remote", "set-url", "--push", "--", &name, &push_url],
+        )?;
+    }
+    Ok(())
+}
+
+/// Remove a remote along with its remote-tracking branches.
+#[tauri::command]
+pub fn remove_remote(repo_path: String, name: String) -> GitResult<()> {
+    check_arg(&name, "remote name")?;
+    run_remote(&repo_path, &["remote", "remove", "--", &name])
+}
+
+/// Rename a remote, updating its tracking branches and upstream settings.
+#[tauri::command]
+pub fn rename_remote(repo_path: String, old_name: String, new_name: String) -> GitResult<()> {
+    check_arg(&old_name, "remote name")?;
+    check_arg(&new_name, "remote name")?;
+    run_remote(
+        &repo_path,
+        &["remote", "rename", "--", &old_name, &new_name],
+    )
+}
+
+/// Change a remote's fetch URL, or its push URL when `push` is set.
+/// `None` with `push` removes the separate push URL.
+#[tauri::command]
+pub fn set_remote_url(
+    repo_path: String,
+    name: String,
+    url: Option<String>,
+    push: bool,
+) -> GitResult<()> {
+    check_arg(&name, "remote name")?;
+    match url.filter(|u| !u.trim().is_empty()) {
+        Some(url) if push => run_remote(
+            &repo_path,
+            &["remote", "set-url", "--push", "--", &name, &url],
+        ),
+        Some(url) => run_remote(&repo_path, &["remote", "set-url", "--", &name, &url]),
+        None if push => {
+            let key = format!("remote.{}.pushurl", name);
+            let out = git_run(&repo_path, &["config", "--unset-all", &key])?;
+            // Exit code 5: the key wasn't set
+            if out.success || out.code == 5 {
+                Ok(())
             } else {
-                None
+                Err(GitError::from_output(&out))
             }
-        })
-        .collect();
-    Ok(remotes)
+        }
+        None => Err("Remote URL is empty".into()),
+    }
+}
+
+/// Set whether fetching `name` prunes deleted branches; `None` clears the
+/// setting so `fetch.prune` applies.
+#[tauri::command]
+pub fn set_remote_prune(repo_path: String, name: String, prune: Option<bool>) -> GitResult<()> {
+    check_arg(&name, "remote name")?;
+    let key = format!("remote.{}.prune", name);
+    match prune {
+        Some(prune) => run_remote(
+            &repo_path,
+            &[
+                "config",
+                "--bool",
+                &key,
+                if prune { "true" } else { "false" },
+            ],
+        ),
+        None => {
+            let out = git_run(&repo_path, &["config", "--unset-all", &key])?;
+            if out.success || out.code == 5 {
+                Ok(())
+            } else {
+                Err(GitError::from_output(&out))
+            }
+        }
+    }
 }
 
 /// Create a new GitHub repository and add it as the `origin` remote.
@@ -42,7 +198,7 @@ pub async fn create_github_repo(
     name: String,
     private: bool,
     description: Option<String>,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let client = Client::new();
 
     // 1. Create repo via GitHub REST API
@@ -60,8 +216,7 @@ pub async fn create_github_repo(
         .header("User-Agent", "EasyGit")
         .json(&body)
         .send()
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     if !res.status().is_success() {
         let status = res.status().as_u16();
@@ -71,63 +226,172 @@ pub async fn create_github_repo(
             .ok()
             .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(String::from))
             .unwrap_or_else(|| format!("HTTP {}: {}", status, text));
-        return Err(msg);
+        return Err(GitError::new(GitErrorKind::GitHub, msg));
     }
 
-    let repo_json: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
+    let repo_json: serde_json::Value = res.json().await?;
     let clone_url = repo_json
         .get("clone_url")
         .and_then(|v| v.as_str())
-        .ok_or("No clone_url in response")?
+        .ok_or_else(|| GitError::new(GitErrorKind::GitHub, "No clone_url in response"))?
         .to_string();
 
     // 2. Add as origin remote
     let out = git_run(&repo_path, &["remote", "add", "origin", &clone_url])?;
     if !out.success {
-        return Err(out.stderr);
+        return Err(GitError::from_output(&out));
     }
 
     Ok(clone_url)
 }
 
+/// Push, emitting `git-progress` events (operation `"push"`). Without a
+/// remote this is a plain `git push`; `set_upstream` pushes the current
+/// branch to `remote` (default `origin`). A rejecting pre-push hook fails
+/// with `hook_failed`.
 #[tauri::command]
-pub fn push(repo_path: String, set_upstream: bool) -> Result<String, String> {
-    // git push output goes to stderr even on success, so combine both
-    let out = if set_upstream {
-        let branch_out = git_run(&repo_path, &["rev-parse", "--abbrev-ref", "HEAD"])?;
-        if !branch_out.success {
-            return Err(branch_out.stderr);
+pub async fn push(
+    app: tauri::AppHandle,
+    repo_path: String,
+    set_upstream: bool,
+    remote: Option<String>,
+    refspec: Option<String>,
+) -> GitResult<String> {
+    let mut args = vec!["push".to_string(), "--progress".to_string()];
+    if set_upstream {
+        args.push("--set-upstream".to_string());
+    }
+    let refspec = match refspec {
+        None if set_upstream => {
+            let branch_out = git_run(&repo_path, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+            if !branch_out.success {
+                return Err(GitError::from_output(&branch_out));
+            }
+            Some(branch_out.stdout.trim().to_string())
         }
-        let branch = branch_out.stdout.trim().to_string();
-        git_run(&repo_path, &["push", "--set-upstream", "origin", &branch])?
-    } else {
-        git_run(&repo_path, &["push"])?
+        refspec => refspec,
     };
+    remote_target(&mut args, remote, refspec)?;
+
+    // git push output goes to stderr even on success, so combine both
+    let out = run_blocking(move || {
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        git_run_with_hooks(&app, &repo_path, &arg_refs, "push")
+    })
+    .await?;
     if out.success {
         let msg = format!("{}{}", out.stdout.trim(), out.stderr.trim());
         Ok(msg)
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
+    }
+}
+
+/// Append `remote [refspec]` to `args`; a refspec without a remote uses
+/// `origin`.
+fn remote_target(
+    args: &mut Vec<String>,
+    remote: Option<String>,
+    refspec: Option<String>,
+) -> GitResult<()> {
+    let remote = match remote {
+        Some(remote) => remote,
+        None if refspec.is_some() => "origin".to_string(),
+        None => return Ok(()),
+    };
+    check_arg(&remote, "remote")?;
+    args.push(remote);
+    if let Some(refspec) = refspec {
+        check_arg(&refspec, "refspec")?;
+        args.push(refspec);
     }
+    Ok(())
 }
 
+/// Pull, emitting `git-progress` events (operation `"pull"`). Without a
+/// remote the current branch's upstream is used.
 #[tauri::command]
-pub fn pull(repo_path: String) -> Result<String, String> {
-    let out = git_run(&repo_path, &["pull"])?;
+pub async fn pull(
+    app: tauri::AppHandle,
+    repo_path: String,
+    remote: Option<String>,
+    refspec: Option<String>,
+) -> GitResult<String> {
+    let mut args = vec!["pull".to_string(), "--progress".to_string()];
+    remote_target(&mut args, remote, refspec)?;
+    let out = run_blocking(move || {
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        git_run_streaming(&app, &repo_path, &arg_refs, "pull")
+    })
+    .await?;
     if out.success {
         Ok(out.stdout.trim().to_string())
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
     }
 }
 
+/// Fetch `remote` (all remotes when None), emitting `git-progress` events
+/// (operation `"fetch"`). `prune` overrides the configured setting; with
+/// neither, stale remote-tracking branches are pruned.
 #[tauri::command]
-pub fn fetch(repo_path: String) -> Result<String, String> {
-    let out = git_run(&repo_path, &["fetch", "--all", "--prune"])?;
+pub async fn fetch(
+    app: tauri::AppHandle,
+    repo_path: String,
+    remote: Option<String>,
+    refspec: Option<String>,
+    prune: Option<bool>,
+) -> GitResult<String> {
+    let mut args = Vec::new();
+    if prune.is_none() {
+        // Default to pruning, but let remote.<name>.prune still win
+        let configured = git_run(&repo_path, &["config", "--get", "fetch.prune"])?;
+        if !configured.success {
+            args.push("-c".to_string());
+            args.push("fetch.prune=true".to_string());
+        }
+    }
+    args.push("fetch".to_string());
+    args.push("--progress".to_string());
+    match prune {
+        Some(true) => args.push("--prune".to_string()),
+        Some(false) => args.push("--no-prune".to_string()),
+        None => {}
+    }
+    if remote.is_none() && refspec.is_none() {
+        args.push("--all".to_string());
+    }
+    remote_target(&mut args, remote, refspec)?;
+
+    let out = run_blocking(move || {
+        let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        git_run_streaming(&app, &repo_path, &arg_refs, "fetch")
+    })
+    .await?;
     if out.success {
         let msg = format!("{}{}", out.stdout.trim(), out.stderr.trim());
         Ok(msg)
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remotes_keeps_separate_push_url() {
+        let remote_v = "origin\tgit@github.com:me/app.git (fetch)\n\
+                        origin\tgit@github.com:me/app.git (push)\n\
+                        upstream\thttps://github.com/org/app.git (fetch)\n\
+                        upstream\tno_push (push)\n";
+        let remotes = parse_remotes(remote_v, "remote.upstream.prune true\n");
+        assert_eq!(remotes.len(), 2);
+        assert_eq!(remotes[0].push_url, "git@github.com:me/app.git");
+        assert_eq!(remotes[0].prune, None);
+        assert_eq!(remotes[1].url, "https://github.com/org/app.git");
+        assert_eq!(remotes[1].push_url, "no_push");
+        assert_eq!(remotes[1].prune, Some(true));
     }
 }
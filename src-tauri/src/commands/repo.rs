@@ -1,8 +1,11 @@
+use crate::commands::commit_message::{ensure_valid_message, CommitLintConfig};
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
 use crate::commands::git::git_run;
+use crate::commands::hooks::git_run_with_hooks;
+use crate::commands::progress::{git_run_streaming, run_blocking};
+use crate::commands::status::read_status;
 use serde::{Deserialize, Serialize};
-use std::io::BufRead;
-use std::process::Stdio;
-use tauri::Emitter;
+use std::path::Path;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FileStatus {
@@ -17,68 +20,13 @@ pub struct FileStatus {
     pub original_path: Option<String>,
 }
 
-fn parse_status_line(line: &str) -> Option<FileStatus> {
-    if line.len() < 4 {
-        return None;
-    }
-    let x = &line[0..1];
-    let y = &line[1..2];
-    let xy = &line[0..2];
-    let path_part = &line[3..];
-
-    // Handle renames: "new -> old" in porcelain=v1
-    let (path, original_path) = if x == "R" || x == "C" {
-        if let Some(pos) = path_part.find(" -> ") {
-            (
-                path_part[..pos].to_string(),
-                Some(path_part[pos + 4..].to_string()),
-            )
-        } else {
-            (path_part.to_string(), None)
-        }
-    } else {
-        (path_part.to_string(), None)
-    };
-
-    let is_staged = x != " " && x != "?";
-    let is_unstaged = y != " " || xy == "??";
-
-    let staged_status = if x == " " || x == "?" {
-        "".to_string()
-    } else {
-        x.to_string()
-    };
-
-    let unstaged_status = if xy == "??" {
-        "?".to_string()
-    } else if y != " " {
-        y.to_string()
-    } else {
-        "".to_string()
-    };
-
-    Some(FileStatus {
-        path,
-        staged_status,
-        unstaged_status,
-        is_staged,
-        is_unstaged,
-        original_path,
-    })
-}
-
 #[tauri::command]
-pub fn get_status(repo_path: String) -> Result<Vec<FileStatus>, String> {
-    let out = git_run(&repo_path, &["status", "--porcelain=v1", "-u"])?;
-    if !out.success && !out.stderr.is_empty() {
-        return Err(out.stderr);
-    }
-    let files = out.stdout.lines().filter_map(parse_status_line).collect();
-    Ok(files)
+pub fn get_status(repo_path: String) -> GitResult<Vec<FileStatus>> {
+    Ok(read_status(&repo_path)?.files)
 }
 
 #[tauri::command]
-pub fn stage_files(repo_path: String, paths: Vec<String>) -> Result<(), String> {
+pub fn stage_files(repo_path: String, paths: Vec<String>) -> GitResult<()> {
     let path_refs: Vec<&str> = paths.iter().map(String::as_str).collect();
     let mut args = vec!["add", "--"];
     args.extend_from_slice(&path_refs);
@@ -86,12 +34,12 @@ pub fn stage_files(repo_path: String, paths: Vec<String>) -> Result<(), String>
     if out.success {
         Ok(())
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
     }
 }
 
 #[tauri::command]
-pub fn unstage_files(repo_path: String, paths: Vec<String>) -> Result<(), String> {
+pub fn unstage_files(repo_path: String, paths: Vec<String>) -> GitResult<()> {
     let path_refs: Vec<&str> = paths.iter().map(String::as_str).collect();
     let mut args = vec!["restore", "--staged", "--"];
     args.extend_from_slice(&path_refs);
@@ -99,22 +47,140 @@ pub fn unstage_files(repo_path: String, paths: Vec<String>) -> Result<(), String
     if out.success {
         Ok(())
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
     }
 }
 
-#[tauri::command]
-pub fn commit(repo_path: String, message: String) -> Result<String, String> {
-    let out = git_run(&repo_path, &["commit", "-m", &message])?;
+/// Optional flags for `commit` and `amend_commit`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommitOptions {
+    /// Add a `Signed-off-by` trailer (`--signoff`)
+    pub signoff: bool,
+    /// Sign with GPG/SSH (`-S`); uses `user.signingkey` unless `signing_key` is set
+    pub sign: bool,
+    pub signing_key: Option<String>,
+    /// Author override as "Name <email>"
+    pub author: Option<String>,
+    /// Author date override, in any format git accepts
+    pub date: Option<String>,
+    pub allow_empty: bool,
+    /// Skip pre-commit and commit-msg hooks
+    pub no_verify: bool,
+    /// Added as `Co-authored-by` trailers, each "Name <email>"
+    pub co_authors: Vec<String>,
+    /// Lint the message as a Conventional Commit before running git; a
+    /// rejected message fails with `lint_failed`
+    pub lint: Option<CommitLintConfig>,
+}
+
+/// Append `key: value` trailers to `message`, extending an existing trailer
+/// block and skipping values that are already present.
+fn append_trailers(message: &str, key: &str, values: &[String]) -> String {
+    let message = message.trim_end();
+    let lines: Vec<String> = values
+        .iter()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| format!("{}: {}", key, v))
+        .filter(|line| {
+            !message
+                .lines()
+                .any(|l| l.to_lowercase() == line.to_lowercase())
+        })
+        .collect();
+    if lines.is_empty() {
+        return message.to_string();
+    }
+
+    // Trailers must form the last paragraph, so join an existing one
+    let last_paragraph = message.rsplit("\n\n").next().unwrap_or("");
+    let ends_with_trailers = message.contains("\n\n")
+        && last_paragraph.lines().all(|l| {
+            l.split_once(": ")
+                .is_some_and(|(k, _)| !k.is_empty() && !k.contains(' '))
+        });
+    let separator = if ends_with_trailers { "\n" } else { "\n\n" };
+    format!("{}{}{}", message, separator, lines.join("\n"))
+}
+
+fn commit_args(message: &str, options: &CommitOptions, amend: bool) -> Vec<String> {
+    let mut args = vec!["commit".to_string()];
+    if amend {
+        args.push("--amend".to_string());
+    }
+    args.push("-m".to_string());
+    args.push(append_trailers(
+        message,
+        "Co-authored-by",
+        &options.co_authors,
+    ));
+    if options.signoff {
+        args.push("--signoff".to_string());
+    }
+    match options.signing_key.as_deref().filter(|k| !k.is_empty()) {
+        Some(key) => args.push(format!("--gpg-sign={}", key)),
+        None if options.sign => args.push("--gpg-sign".to_string()),
+        None => {}
+    }
+    if let Some(author) = options.author.as_deref().filter(|a| !a.is_empty()) {
+        args.push(format!("--author={}", author));
+    }
+    if let Some(date) = options.date.as_deref().filter(|d| !d.is_empty()) {
+        args.push(format!("--date={}", date));
+    }
+    if options.allow_empty {
+        args.push("--allow-empty".to_string());
+    }
+    if options.no_verify {
+        args.push("--no-verify".to_string());
+    }
+    args
+}
+
+fn run_commit(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    message: &str,
+    options: &CommitOptions,
+    amend: bool,
+) -> GitResult<String> {
+    if let Some(ref config) = options.lint {
+        ensure_valid_message(message, config)?;
+    }
+    let args = commit_args(message, options, amend);
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    let out = git_run_with_hooks(app, repo_path, &arg_refs, "commit")?;
     if out.success {
         Ok(out.stdout.trim().to_string())
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
     }
 }
 
+/// Commit what is staged. Hook output streams as `git-progress` events
+/// (operation `"commit"`); a rejecting hook fails with `hook_failed`.
+#[tauri::command]
+pub async fn commit(
+    app: tauri::AppHandle,
+    repo_path: String,
+    message: String,
+    options: Option<CommitOptions>,
+) -> GitResult<String> {
+    run_blocking(move || {
+        run_commit(
+            &app,
+            &repo_path,
+            &message,
+            &options.unwrap_or_default(),
+            false,
+        )
+    })
+    .await
+}
+
 #[tauri::command]
-pub fn get_current_branch(repo_path: String) -> Result<String, String> {
+pub fn get_current_branch(repo_path: String) -> GitResult<String> {
     // symbolic-ref works even on brand-new repos with no commits yet
     let out = git_run(&repo_path, &["symbolic-ref", "--short", "HEAD"])?;
     if out.success {
@@ -129,57 +195,72 @@ pub fn get_current_branch(repo_path: String) -> Result<String, String> {
     }
 }
 
-/// Clone a remote repository to `dest_path`, emitting `clone-progress` events
-/// for each line of git's stderr output as it arrives.
+/// Clone a remote repository to `dest_path`, emitting `git-progress` events
+/// (operation `"clone"`) as git reports progress.
+/// A cancelled clone removes whatever it already wrote to `dest_path`, unless
+/// `dest_path` already held files (git refuses those, possibly only after
+/// the cancel).
 #[tauri::command]
-pub fn clone_repo(app: tauri::AppHandle, url: String, dest_path: String) -> Result<String, String> {
-    let dest = dest_path.clone();
-
-    let mut child = std::process::Command::new("git")
-        .args(["clone", "--progress", &url, &dest])
-        .stdout(Stdio::null())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to spawn git: {}", e))?;
-
-    let stderr = child.stderr.take().unwrap();
-    let app_clone = app.clone();
-
-    // Read stderr in a dedicated thread so the pipe never blocks.
-    // git writes progress using \r to overwrite lines, and \n at the end of
-    // each logical section. We split on both to capture every update.
-    let reader_handle = std::thread::spawn(move || -> String {
-        let reader = std::io::BufReader::new(stderr);
-        let mut last_line = String::new();
-        for chunk in reader.split(b'\n') {
-            match chunk {
-                Ok(bytes) => {
-                    let text = String::from_utf8_lossy(&bytes);
-                    for part in text.split('\r') {
-                        let trimmed = part.trim();
-                        if !trimmed.is_empty() {
-                            last_line = trimmed.to_string();
-                            let _ = app_clone.emit("clone-progress", trimmed.to_string());
-                        }
-                    }
-                }
-                Err(_) => break,
+pub async fn clone_repo(
+    app: tauri::AppHandle,
+    url: String,
+    dest_path: String,
+) -> GitResult<String> {
+    let dest = Path::new(&dest_path);
+    let dest_existed = dest.exists();
+    // Checked before spawning: a quick cancel can kill git before it
+    // refuses a non-empty target, and those files are the user's.
+    let dest_was_empty = !dest_existed || is_empty_dir(dest);
+
+    let clone_dest = dest_path.clone();
+    let result = run_blocking(move || {
+        git_run_streaming(
+            &app,
+            "",
+            &["clone", "--progress", &url, &clone_dest],
+            "clone",
+        )
+    })
+    .await;
+    let out = match result {
+        Err(e) if e.kind == GitErrorKind::Cancelled => {
+            if dest_was_empty {
+                remove_partial_clone(dest, dest_existed);
             }
+            return Err(e);
         }
-        last_line
-    });
-
-    let status = child.wait().map_err(|e| e.to_string())?;
-    let last_line = reader_handle.join().unwrap_or_default();
+        result => result?,
+    };
 
-    if status.success() {
+    if out.success {
         Ok(dest_path)
     } else {
-        Err(if last_line.is_empty() {
-            "git clone failed".to_string()
-        } else {
-            last_line
-        })
+        Err(GitError::from_output(&out))
+    }
+}
+
+fn is_empty_dir(path: &Path) -> bool {
+    std::fs::read_dir(path)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false)
+}
+
+/// Remove a partially written clone from a `dest` that was missing or empty
+/// before the clone. An existing `dest` is emptied rather than deleted.
+fn remove_partial_clone(dest: &Path, dest_existed: bool) {
+    if !dest_existed {
+        let _ = std::fs::remove_dir_all(dest);
+        return;
+    }
+    if let Ok(entries) = std::fs::read_dir(dest) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let _ = if path.is_dir() {
+                std::fs::remove_dir_all(&path)
+            } else {
+                std::fs::remove_file(&path)
+            };
+        }
     }
 }
 
@@ -190,7 +271,7 @@ pub fn discard_file_changes(
     repo_path: String,
     paths: Vec<String>,
     is_untracked: bool,
-) -> Result<(), String> {
+) -> GitResult<()> {
     let path_refs: Vec<&str> = paths.iter().map(String::as_str).collect();
     let out = if is_untracked {
         let mut args = vec!["clean", "-f", "--"];
@@ -204,28 +285,80 @@ pub fn discard_file_changes(
     if out.success {
         Ok(())
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
     }
 }
 
 /// Amend the most recent commit with a new message (and whatever is staged).
 #[tauri::command]
-pub fn amend_commit(repo_path: String, message: String) -> Result<String, String> {
-    let out = git_run(&repo_path, &["commit", "--amend", "-m", &message])?;
-    if out.success {
-        Ok(out.stdout.trim().to_string())
-    } else {
-        Err(out.stderr)
-    }
+pub async fn amend_commit(
+    app: tauri::AppHandle,
+    repo_path: String,
+    message: String,
+    options: Option<CommitOptions>,
+) -> GitResult<String> {
+    run_blocking(move || {
+        run_commit(
+            &app,
+            &repo_path,
+            &message,
+            &options.unwrap_or_default(),
+            true,
+        )
+    })
+    .await
 }
 
 /// Return the full message of the most recent commit (subject + body).
 #[tauri::command]
-pub fn get_last_commit_message(repo_path: String) -> Result<String, String> {
+pub fn get_last_commit_message(repo_path: String) -> GitResult<String> {
     let out = git_run(&repo_path, &["log", "-1", "--pretty=%B"])?;
     if out.success {
         Ok(out.stdout.trim().to_string())
     } else {
-        Err(out.stderr)
+        Err(GitError::from_output(&out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_co_author_trailers() {
+        let co = vec!["Bob <bob@x.io>".to_string()];
+        assert_eq!(
+            append_trailers("feat: add x", "Co-authored-by", &co),
+            "feat: add x\n\nCo-authored-by: Bob <bob@x.io>"
+        );
+        assert_eq!(
+            append_trailers("fix: y\n\nRefs: #12\n", "Co-authored-by", &co),
+            "fix: y\n\nRefs: #12\nCo-authored-by: Bob <bob@x.io>"
+        );
+        // Already present: unchanged
+        let msg = "fix: y\n\nCo-authored-by: Bob <bob@x.io>";
+        assert_eq!(append_trailers(msg, "Co-authored-by", &co), msg);
+    }
+
+    #[test]
+    fn test_commit_args_with_options() {
+        let options = CommitOptions {
+            signoff: true,
+            signing_key: Some("ABCD1234".into()),
+            allow_empty: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            commit_args("chore: x", &options, true),
+            [
+                "commit",
+                "--amend",
+                "-m",
+                "chore: x",
+                "--signoff",
+                "--gpg-sign=ABCD1234",
+                "--allow-empty"
+            ]
+        );
     }
 }
@@ -1,3 +1,4 @@
+use crate::commands::error::{GitError, GitResult};
 use crate::commands::git::git_run;
 use serde::{Deserialize, Serialize};
 
@@ -10,14 +11,14 @@ pub struct StashInfo {
 }
 
 #[tauri::command]
-pub async fn list_stashes(repo_path: String) -> Result<Vec<StashInfo>, String> {
+pub async fn list_stashes(repo_path: String) -> GitResult<Vec<StashInfo>> {
     let out = git_run(
         &repo_path,
         &["stash", "list", "--format=%H|%gd|%gs"],
     )?;
     // not an error if there are no stashes
     if !out.success && !out.stderr.trim().is_empty() {
-        return Err(out.stderr.trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     let stashes = out
         .stdout
@@ -44,7 +45,7 @@ pub async fn stash_push(
     repo_path: String,
     message: Option<String>,
     include_untracked: bool,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let mut args = vec!["stash", "push"];
     if include_untracked {
         args.push("-u");
@@ -57,7 +58,7 @@ pub async fn stash_push(
     }
     let out = git_run(&repo_path, &args)?;
     if !out.success {
-        return Err(format!("{}\n{}", out.stdout, out.stderr).trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(out.stdout.trim().to_string())
 }
@@ -66,12 +67,12 @@ pub async fn stash_push(
 pub async fn stash_pop(
     repo_path: String,
     index: usize,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let reference = format!("stash@{{{}}}", index);
     let ref_str = reference.as_str();
     let out = git_run(&repo_path, &["stash", "pop", ref_str])?;
     if !out.success {
-        return Err(format!("{}\n{}", out.stdout, out.stderr).trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(out.stdout.trim().to_string())
 }
@@ -80,12 +81,12 @@ pub async fn stash_pop(
 pub async fn stash_apply(
     repo_path: String,
     index: usize,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let reference = format!("stash@{{{}}}", index);
     let ref_str = reference.as_str();
     let out = git_run(&repo_path, &["stash", "apply", ref_str])?;
     if !out.success {
-        return Err(format!("{}\n{}", out.stdout, out.stderr).trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(out.stdout.trim().to_string())
 }
@@ -94,12 +95,12 @@ pub async fn stash_apply(
 pub async fn stash_drop(
     repo_path: String,
     index: usize,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let reference = format!("stash@{{{}}}", index);
     let ref_str = reference.as_str();
     let out = git_run(&repo_path, &["stash", "drop", ref_str])?;
     if !out.success {
-        return Err(format!("{}\n{}", out.stdout, out.stderr).trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(out.stdout.trim().to_string())
 }
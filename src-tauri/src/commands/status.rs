@@ -0,0 +1,332 @@
+use crate::commands::error::{GitError, GitResult};
+use crate::commands::git::git_run;
+use crate::commands::repo::FileStatus;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Changed,
+    Renamed,
+    Copied,
+    Unmerged,
+    Untracked,
+    Ignored,
+}
+
+/// Which sides of a conflict touched the path (from the unmerged XY code).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnmergedKind {
+    BothDeleted,
+    AddedByUs,
+    DeletedByThem,
+    AddedByThem,
+    DeletedByUs,
+    BothAdded,
+    BothModified,
+}
+
+impl UnmergedKind {
+    fn from_xy(xy: &str) -> Option<Self> {
+        Some(match xy {
+            "DD" => UnmergedKind::BothDeleted,
+            "AU" => UnmergedKind::AddedByUs,
+            "UD" => UnmergedKind::DeletedByThem,
+            "UA" => UnmergedKind::AddedByThem,
+            "DU" => UnmergedKind::DeletedByUs,
+            "AA" => UnmergedKind::BothAdded,
+            "UU" => UnmergedKind::BothModified,
+            _ => return None,
+        })
+    }
+}
+
+/// Octal file modes ("100644", "100755", "120000", "160000" …); "000000"
+/// where the file doesn't exist. For unmerged paths `head` is our side and
+/// `index` is empty, since the index holds several stages.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileModes {
+    pub head: String,
+    pub index: String,
+    pub worktree: String,
+}
+
+/// State of a submodule entry (the `S<c><m><u>` field).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmoduleState {
+    /// Checked-out commit differs from the recorded one
+    pub commit_changed: bool,
+    pub has_tracked_changes: bool,
+    pub has_untracked_changes: bool,
+}
+
+/// One path from `git status --porcelain=v2`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEntry {
+    pub path: String,
+    /// Source path of a rename or copy
+    pub original_path: Option<String>,
+    pub kind: EntryKind,
+    /// Index status character, "" when unchanged
+    pub index_status: String,
+    /// Worktree status character, "" when unchanged
+    pub worktree_status: String,
+    /// Rename/copy similarity in percent
+    pub similarity: Option<u8>,
+    /// None for untracked and ignored paths
+    pub modes: Option<FileModes>,
+    pub submodule: Option<SubmoduleState>,
+    pub unmerged: Option<UnmergedKind>,
+}
+
+impl StatusEntry {
+    /// The porcelain=v1 style summary the changes list works with.
+    pub fn to_file_status(&self) -> FileStatus {
+        let untracked = self.kind == EntryKind::Untracked;
+        FileStatus {
+            path: self.path.clone(),
+            staged_status: self.index_status.clone(),
+            unstaged_status: if untracked {
+                "?".to_string()
+            } else {
+                self.worktree_status.clone()
+            },
+            is_staged: !self.index_status.is_empty(),
+            is_unstaged: untracked || !self.worktree_status.is_empty(),
+            original_path: self.original_path.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatusSnapshot {
+    /// None when HEAD is detached
+    pub branch: Option<String>,
+    /// None before the first commit
+    pub head: Option<String>,
+    pub upstream: Option<String>,
+    /// Only meaningful when `upstream` is set (and still exists)
+    pub ahead: u32,
+    pub behind: u32,
+    pub stash_count: u32,
+    pub files: Vec<FileStatus>,
+    pub entries: Vec<StatusEntry>,
+}
+
+fn status_char(c: char) -> String {
+    if c == '.' {
+        String::new()
+    } else {
+        c.to_string()
+    }
+}
+
+fn parse_submodule(field: &str) -> Option<SubmoduleState> {
+    let flags: Vec<char> = field.strip_prefix('S')?.chars().collect();
+    Some(SubmoduleState {
+        commit_changed: flags.first() == Some(&'C'),
+        has_tracked_changes: flags.get(1) == Some(&'M'),
+        has_untracked_changes: flags.get(2) == Some(&'U'),
+    })
+}
+
+/// Parse `git status --porcelain=v2 --branch --show-stash -z` output.
+/// Paths are NUL-separated and never quoted.
+pub fn parse_status_v2(stdout: &str) -> StatusSnapshot {
+    let mut snapshot = StatusSnapshot::default();
+    let mut records = stdout.split('\0');
+
+    while let Some(record) = records.next() {
+        if let Some(header) = record.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.oid" if value != "(initial)" => snapshot.head = Some(value.to_string()),
+                "branch.head" if value != "(detached)" => snapshot.branch = Some(value.to_string()),
+                "branch.upstream" => snapshot.upstream = Some(value.to_string()),
+                "branch.ab" => {
+                    for n in value.split_whitespace() {
+                        if let Some(ahead) = n.strip_prefix('+') {
+                            snapshot.ahead = ahead.parse().unwrap_or(0);
+                        } else if let Some(behind) = n.strip_prefix('-') {
+                            snapshot.behind = behind.parse().unwrap_or(0);
+                        }
+                    }
+                }
+                "stash" => snapshot.stash_count = value.parse().unwrap_or(0),
+                _ => {}
+            }
+            continue;
+        }
+
+        let rest = record.get(2..).unwrap_or("");
+        let entry = match record.get(..2) {
+            Some("1 ") => {
+                // <XY> <sub> <mH> <mI> <mW> <hH> <hI> <path>
+                let f: Vec<&str> = rest.splitn(8, ' ').collect();
+                let [xy, sub, m_head, m_index, m_wt, _, _, path] = f[..] else {
+                    continue;
+                };
+                changed_entry(xy, sub, [m_head, m_index, m_wt], path, EntryKind::Changed)
+            }
+            Some("2 ") => {
+                // <XY> <sub> <mH> <mI> <mW> <hH> <hI> <X><score> <path>\0<origPath>
+                let f: Vec<&str> = rest.splitn(9, ' ').collect();
+                let [xy, sub, m_head, m_index, m_wt, _, _, score, path] = f[..] else {
+                    continue;
+                };
+                let kind = if score.starts_with('C') {
+                    EntryKind::Copied
+                } else {
+                    EntryKind::Renamed
+                };
+                let mut entry = changed_entry(xy, sub, [m_head, m_index, m_wt], path, kind);
+                entry.similarity = score.get(1..).and_then(|s| s.parse().ok());
+                entry.original_path = records.next().map(str::to_string);
+                entry
+            }
+            Some("u ") => {
+                // <XY> <sub> <m1> <m2> <m3> <mW> <h1> <h2> <h3> <path>
+                let f: Vec<&str> = rest.splitn(10, ' ').collect();
+                let [xy, sub, _, m_ours, _, m_wt, _, _, _, path] = f[..] else {
+                    continue;
+                };
+                let mut entry =
+                    changed_entry(xy, sub, [m_ours, "", m_wt], path, EntryKind::Unmerged);
+                entry.unmerged = UnmergedKind::from_xy(xy);
+                entry
+            }
+            Some("? ") => plain_entry(rest, EntryKind::Untracked),
+            Some("! ") => plain_entry(rest, EntryKind::Ignored),
+            _ => continue,
+        };
+        snapshot.entries.push(entry);
+    }
+
+    snapshot.files = snapshot
+        .entries
+        .iter()
+        .filter(|e| e.kind != EntryKind::Ignored)
+        .map(StatusEntry::to_file_status)
+        .collect();
+    snapshot
+}
+
+fn changed_entry(
+    xy: &str,
+    sub: &str,
+    [head, index, worktree]: [&str; 3],
+    path: &str,
+    kind: EntryKind,
+) -> StatusEntry {
+    let mut chars = xy.chars();
+    StatusEntry {
+        path: path.to_string(),
+        original_path: None,
+        kind,
+        index_status: chars.next().map(status_char).unwrap_or_default(),
+        worktree_status: chars.next().map(status_char).unwrap_or_default(),
+        similarity: None,
+        modes: Some(FileModes {
+            head: head.to_string(),
+            index: index.to_string(),
+            worktree: worktree.to_string(),
+        }),
+        submodule: parse_submodule(sub),
+        unmerged: None,
+    }
+}
+
+fn plain_entry(path: &str, kind: EntryKind) -> StatusEntry {
+    StatusEntry {
+        path: path.to_string(),
+        original_path: None,
+        kind,
+        index_status: String::new(),
+        worktree_status: String::new(),
+        similarity: None,
+        modes: None,
+        submodule: None,
+        unmerged: None,
+    }
+}
+
+/// Run `git status` in porcelain v2 form and parse it.
+pub fn read_status(repo_path: &str) -> GitResult<StatusSnapshot> {
+    let out = git_run(
+        repo_path,
+        &[
+            "status",
+            "--porcelain=v2",
+            "--branch",
+            "--show-stash",
+            "-z",
+            "-u",
+        ],
+    )?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    Ok(parse_status_v2(&out.stdout))
+}
+
+/// Full status: branch, upstream tracking, stash count and per-path
+/// details, plus the `FileStatus` list `get_status` returns.
+#[tauri::command]
+pub fn get_status_snapshot(repo_path: String) -> GitResult<StatusSnapshot> {
+    read_status(&repo_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_v2() {
+        let out = concat!(
+            "# branch.oid 1111\0",
+            "# branch.head feature/x\0",
+            "# branch.upstream origin/feature/x\0",
+            "# branch.ab +2 -1\0",
+            "# stash 3\0",
+            "1 .M N... 100644 100644 100755 aaa aaa sp ace ü.txt\0",
+            "2 R. N... 100644 100644 100644 bbb bbb R87 new name.rs\0old name.rs\0",
+            "1 .M SC.U 160000 160000 160000 ccc ccc vendor/lib\0",
+            "u UU N... 100644 100644 100644 100644 d1 d2 d3 both.txt\0",
+            "? untracked file\0",
+        );
+        let s = parse_status_v2(out);
+        assert_eq!(s.branch.as_deref(), Some("feature/x"));
+        assert_eq!(s.head.as_deref(), Some("1111"));
+        assert_eq!(s.upstream.as_deref(), Some("origin/feature/x"));
+        assert_eq!((s.ahead, s.behind, s.stash_count), (2, 1, 3));
+        assert_eq!(s.entries.len(), 5);
+
+        assert_eq!(s.entries[0].path, "sp ace ü.txt");
+        assert_eq!(s.entries[0].modes.as_ref().unwrap().worktree, "100755");
+
+        let renamed = &s.entries[1];
+        assert_eq!(renamed.kind, EntryKind::Renamed);
+        assert_eq!(renamed.path, "new name.rs");
+        assert_eq!(renamed.original_path.as_deref(), Some("old name.rs"));
+        assert_eq!(renamed.similarity, Some(87));
+
+        let sub = s.entries[2].submodule.unwrap();
+        assert!(sub.commit_changed && !sub.has_tracked_changes && sub.has_untracked_changes);
+        assert_eq!(s.entries[3].unmerged, Some(UnmergedKind::BothModified));
+
+        assert_eq!(s.files[1].staged_status, "R");
+        assert!(s.files[1].is_staged && !s.files[1].is_unstaged);
+        assert_eq!(s.files[3].staged_status, "U");
+        assert_eq!(s.files[4].unstaged_status, "?");
+        assert!(!s.files[4].is_staged && s.files[4].is_unstaged);
+    }
+
+    #[test]
+    fn test_parse_status_v2_initial_detached() {
+        let s = parse_status_v2("# branch.oid (initial)\0# branch.head (detached)\0");
+        assert_eq!(s.head, None);
+        assert_eq!(s.branch, None);
+        assert!(s.entries.is_empty());
+    }
+}
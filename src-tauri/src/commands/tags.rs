@@ -1,37 +1,99 @@
-use crate::commands::git::git_run;
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
+use crate::commands::git::{git_run, GitOutput};
+use crate::commands::history::SignatureStatus;
+use crate::commands::hooks::git_run_with_hooks;
+use crate::commands::progress::run_blocking;
+use crate::commands::remote::check_arg;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TagInfo {
     pub name: String,
+    /// Short hash of the tagged commit (peeled for annotated tags)
     pub commit_hash: String,
     pub date: String,
     pub message: Option<String>,
+    /// False for lightweight tags
+    pub annotated: bool,
+    /// Annotated tag carrying a GPG/SSH signature
+    pub signed: bool,
+    pub tagger: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagKind {
+    Lightweight,
+    #[default]
+    Annotated,
+    /// Annotated and signed with GPG/SSH (`-s`, or `-u <key>`)
+    Signed,
+}
+
+/// Optional settings for `create_tag`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TagOptions {
+    pub kind: TagKind,
+    /// Commit (or any revision) to tag; defaults to HEAD
+    pub target: Option<String>,
+    /// Key for signed tags; uses `user.signingkey` when unset
+    pub signing_key: Option<String>,
+    /// Move an existing tag instead of failing
+    pub force: bool,
+}
+
+/// A tag as advertised by a remote.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RemoteTag {
+    pub name: String,
+    /// Full hash of the tagged commit
+    pub commit_hash: String,
+    pub exists_locally: bool,
+    /// A local tag of the same name points at a different commit
+    pub differs: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagVerification {
+    pub status: SignatureStatus,
+    pub signer: Option<String>,
+    pub key: Option<String>,
+    /// gpg/ssh-keygen output
+    pub output: String,
 }
 
 /// List local tags sorted newest-first.
 #[tauri::command]
-pub fn list_tags(repo_path: String) -> Result<Vec<TagInfo>, String> {
+pub fn list_tags(repo_path: String) -> GitResult<Vec<TagInfo>> {
     let out = git_run(
         &repo_path,
         &[
             "tag",
             "-l",
             "--sort=-creatordate",
-            "--format=%(refname:short)|%(objectname:short)|%(creatordate:short)|%(contents:subject)",
+            "--format=%(refname:short)|%(objectname:short)|%(*objectname:short)|%(objecttype)|\
+%(if)%(contents:signature)%(then)1%(end)|%(creatordate:short)|%(taggername)|%(contents:subject)",
         ],
     )?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
 
     let tags = out
         .stdout
         .lines()
         .filter(|l| !l.trim().is_empty())
         .map(|line| {
-            let mut parts = line.splitn(4, '|');
+            let mut parts = line.splitn(8, '|');
             let name = parts.next().unwrap_or("").trim().to_string();
-            let commit_hash = parts.next().unwrap_or("").trim().to_string();
+            let object = parts.next().unwrap_or("").trim();
+            let peeled = parts.next().unwrap_or("").trim();
+            let annotated = parts.next().unwrap_or("").trim() == "tag";
+            let signed = parts.next().unwrap_or("").trim() == "1";
             let date = parts.next().unwrap_or("").trim().to_string();
+            let tagger_raw = parts.next().unwrap_or("").trim().to_string();
             let message_raw = parts.next().unwrap_or("").trim().to_string();
             let message = if message_raw.is_empty() {
                 None
@@ -40,9 +102,12 @@ pub fn list_tags(repo_path: String) -> Result<Vec<TagInfo>, String> {
             };
             TagInfo {
                 name,
-                commit_hash,
+                commit_hash: if peeled.is_empty() { object } else { peeled }.to_string(),
                 date,
                 message,
+                annotated,
+                signed,
+                tagger: (!tagger_raw.is_empty()).then_some(tagger_raw),
             }
         })
         .collect();
@@ -50,40 +115,244 @@ pub fn list_tags(repo_path: String) -> Result<Vec<TagInfo>, String> {
     Ok(tags)
 }
 
-/// Create an annotated local tag.
+fn tag_args(name: &str, message: &str, options: &TagOptions) -> GitResult<Vec<String>> {
+    let mut args = vec!["tag".to_string()];
+    if options.kind != TagKind::Lightweight {
+        if message.trim().is_empty() {
+            return Err("Annotated tags need a message".into());
+        }
+        match options.signing_key.as_deref().filter(|k| !k.is_empty()) {
+            Some(key) if options.kind == TagKind::Signed => {
+                args.push(format!("--local-user={}", key))
+            }
+            _ if options.kind == TagKind::Signed => args.push("--sign".to_string()),
+            _ => args.push("--annotate".to_string()),
+        }
+        args.push("-m".to_string());
+        args.push(message.to_string());
+    }
+    if options.force {
+        args.push("--force".to_string());
+    }
+    args.push("--".to_string());
+    args.push(name.to_string());
+    if let Some(target) = options.target.as_deref().filter(|t| !t.is_empty()) {
+        args.push(target.to_string());
+    }
+    Ok(args)
+}
+
+/// Create a local tag, annotated unless `options.kind` says otherwise.
+/// `message` is ignored for lightweight tags.
 #[tauri::command]
-pub fn create_tag(repo_path: String, name: String, message: String) -> Result<String, String> {
-    git_run(&repo_path, &["tag", "-a", &name, "-m", &message])?;
+pub fn create_tag(
+    repo_path: String,
+    name: String,
+    message: String,
+    options: Option<TagOptions>,
+) -> GitResult<String> {
+    let args = tag_args(&name, &message, &options.unwrap_or_default())?;
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    let out = git_run(&repo_path, &arg_refs)?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
     Ok(format!("Tag '{}' created", name))
 }
 
 /// Delete a local tag.
 #[tauri::command]
-pub fn delete_tag(repo_path: String, name: String) -> Result<String, String> {
-    git_run(&repo_path, &["tag", "-d", &name])?;
+pub fn delete_tag(repo_path: String, name: String) -> GitResult<String> {
+    let out = git_run(&repo_path, &["tag", "-d", "--", &name])?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
     Ok(format!("Tag '{}' deleted", name))
 }
 
-/// Push a tag to origin.
+/// Push a tag to `remote` (default `origin`), emitting `git-progress`
+/// events (operation `"push"`). A rejecting pre-push hook fails with
+/// `hook_failed`.
 #[tauri::command]
-pub fn push_tag(repo_path: String, tag_name: String) -> Result<String, String> {
-    git_run(&repo_path, &["push", "origin", &tag_name])?;
-    Ok(format!("Tag '{}' pushed", tag_name))
+pub async fn push_tag(
+    app: tauri::AppHandle,
+    repo_path: String,
+    tag_name: String,
+    remote: Option<String>,
+) -> GitResult<String> {
+    let remote = remote.unwrap_or_else(|| "origin".to_string());
+    check_arg(&remote, "remote")?;
+    check_arg(&tag_name, "tag name")?;
+    let refspec = format!("refs/tags/{}", tag_name);
+    let push_remote = remote.clone();
+    let out = run_blocking(move || {
+        git_run_with_hooks(
+            &app,
+            &repo_path,
+            &["push", "--progress", "--", &push_remote, &refspec],
+            "push",
+        )
+    })
+    .await?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    Ok(format!("Tag '{}' pushed to {}", tag_name, remote))
 }
 
-/// Delete a tag from the remote.
+/// Delete a tag from `remote` (default `origin`).
 #[tauri::command]
-pub fn delete_remote_tag(repo_path: String, tag_name: String) -> Result<String, String> {
-    git_run(&repo_path, &["push", "origin", "--delete", &tag_name])?;
-    Ok(format!("Remote tag '{}' deleted", tag_name))
+pub async fn delete_remote_tag(
+    app: tauri::AppHandle,
+    repo_path: String,
+    tag_name: String,
+    remote: Option<String>,
+) -> GitResult<String> {
+    let remote = remote.unwrap_or_else(|| "origin".to_string());
+    check_arg(&remote, "remote")?;
+    check_arg(&tag_name, "tag name")?;
+    let refspec = format!(":refs/tags/{}", tag_name);
+    let push_remote = remote.clone();
+    let out = run_blocking(move || {
+        git_run_with_hooks(
+            &app,
+            &repo_path,
+            &["push", "--progress", "--", &push_remote, &refspec],
+            "push",
+        )
+    })
+    .await?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    Ok(format!("Remote tag '{}' deleted from {}", tag_name, remote))
 }
 
-/// Returns one-line commit messages since the given tag (or the last 100 if no tag).
+/// Parse `<hash> refs/tags/<name>` lines as printed by `ls-remote --tags`
+/// and `show-ref -d --tags` into `(name, commit)`, preferring the peeled
+/// `^{}` line of annotated tags.
+fn parse_tag_refs(stdout: &str) -> Vec<(String, String)> {
+    let mut tags: Vec<(String, String)> = Vec::new();
+    for line in stdout.lines() {
+        let Some((hash, refname)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+        let Some(name) = refname.trim().strip_prefix("refs/tags/") else {
+            continue;
+        };
+        match name.strip_suffix("^{}") {
+            Some(name) => match tags.iter_mut().find(|(n, _)| n == name) {
+                Some(tag) => tag.1 = hash.to_string(),
+                None => tags.push((name.to_string(), hash.to_string())),
+            },
+            None => tags.push((name.to_string(), hash.to_string())),
+        }
+    }
+    tags
+}
+
+/// Tags on `remote` (default `origin`), flagged when missing locally or
+/// pointing elsewhere.
 #[tauri::command]
-pub fn get_commits_since_tag(
+pub async fn list_remote_tags(
     repo_path: String,
-    tag: Option<String>,
-) -> Result<Vec<String>, String> {
+    remote: Option<String>,
+) -> GitResult<Vec<RemoteTag>> {
+    let remote = remote.unwrap_or_else(|| "origin".to_string());
+    check_arg(&remote, "remote")?;
+    let out = git_run(&repo_path, &["ls-remote", "--tags", "--", &remote])?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    // show-ref exits 1 when there are no tags at all
+    let local = parse_tag_refs(&git_run(&repo_path, &["show-ref", "-d", "--tags"])?.stdout);
+
+    Ok(parse_tag_refs(&out.stdout)
+        .into_iter()
+        .map(|(name, commit_hash)| {
+            let local_hash = local.iter().find(|(n, _)| *n == name).map(|(_, h)| h);
+            RemoteTag {
+                exists_locally: local_hash.is_some(),
+                differs: local_hash.is_some_and(|h| *h != commit_hash),
+                name,
+                commit_hash,
+            }
+        })
+        .collect())
+}
+
+/// Interpret `git tag -v` output (gpg or ssh-keygen on stderr).
+fn parse_verification(out: &GitOutput) -> TagVerification {
+    let output = out.stderr.trim().to_string();
+    let lower = output.to_lowercase();
+    let status = if lower.contains("no signature found") {
+        SignatureStatus::Unsigned
+    } else if lower.contains("bad signature") || lower.contains("signature verification failed") {
+        SignatureStatus::Bad
+    } else if lower.contains("expired key") || lower.contains("key has expired") {
+        SignatureStatus::ExpiredKey
+    } else if lower.contains("signature has expired") || lower.contains("expired signature") {
+        SignatureStatus::Expired
+    } else if lower.contains("revoked") {
+        SignatureStatus::RevokedKey
+    } else if lower.contains("good") && lower.contains("not certified with a trusted") {
+        SignatureStatus::UnknownValidity
+    } else if out.success {
+        SignatureStatus::Good
+    } else {
+        SignatureStatus::CannotCheck
+    };
+
+    // gpg: `Good signature from "Name <mail>"`, `using RSA key ABCD`
+    // ssh: `Good "git" signature for mail with ED25519 key SHA256:…`
+    let signer = output.lines().find_map(|l| {
+        if let Some((_, rest)) = l.split_once(" signature from \"") {
+            return rest.split('"').next().map(str::to_string);
+        }
+        let (_, rest) = l.split_once(" signature for ")?;
+        rest.split(" with ").next().map(str::to_string)
+    });
+    let key = output.lines().find_map(|l| {
+        let (_, rest) = l.split_once(" key ")?;
+        rest.split_whitespace().last().map(str::to_string)
+    });
+    TagVerification {
+        status,
+        signer,
+        key,
+        output,
+    }
+}
+
+/// Check the GPG/SSH signature of an annotated tag. Unsigned and
+/// lightweight tags report `unsigned` rather than failing.
+#[tauri::command]
+pub fn verify_tag(repo_path: String, name: String) -> GitResult<TagVerification> {
+    let kind = git_run(
+        &repo_path,
+        &["cat-file", "-t", &format!("refs/tags/{}", name)],
+    )?;
+    if !kind.success {
+        return Err(GitError::new(
+            GitErrorKind::NotFound,
+            format!("Tag '{}' not found", name),
+        ));
+    }
+    if kind.stdout.trim() != "tag" {
+        return Ok(TagVerification {
+            status: SignatureStatus::Unsigned,
+            signer: None,
+            key: None,
+            output: String::new(),
+        });
+    }
+    let out = git_run(&repo_path, &["tag", "-v", "--", &name])?;
+    Ok(parse_verification(&out))
+}
+
+/// Returns one-line commit messages since the given tag (or the last 100 if no tag).
+#[tauri::command]
+pub fn get_commits_since_tag(repo_path: String, tag: Option<String>) -> GitResult<Vec<String>> {
     let range = match &tag {
         Some(t) => format!("{}..HEAD", t),
         None => String::new(),
@@ -96,29 +365,25 @@ pub fn get_commits_since_tag(
         args.extend_from_slice(&["-n", "100"]);
     }
 
-    let out = git_run(&repo_path, &args);
-    match out {
-        Ok(o) => {
-            let lines: Vec<String> = o
-                .stdout
-                .lines()
-                .filter(|l| !l.is_empty())
-                .map(|l| l.to_string())
-                .collect();
-            Ok(lines)
-        }
-        Err(e) => {
-            // Empty repo has no commits
-            if e.contains("does not have any commits")
-                || e.contains("bad default revision")
-                || e.contains("unknown revision")
-            {
-                Ok(vec![])
-            } else {
-                Err(e)
-            }
+    let out = git_run(&repo_path, &args)?;
+    if !out.success {
+        let err = GitError::from_output(&out);
+        // Empty repo has no commits
+        if matches!(
+            err.kind,
+            GitErrorKind::EmptyRepository | GitErrorKind::NotFound
+        ) {
+            return Ok(vec![]);
         }
+        return Err(err);
     }
+    let lines: Vec<String> = out
+        .stdout
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.to_string())
+        .collect();
+    Ok(lines)
 }
 
 /// Use GitHub's native release-notes generator.
@@ -130,7 +395,7 @@ pub async fn generate_github_release_notes(
     repo: String,
     tag_name: String,
     previous_tag_name: Option<String>,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let client = Client::new();
 
     let mut payload = serde_json::json!({ "tag_name": tag_name });
@@ -152,16 +417,18 @@ pub async fn generate_github_release_notes(
         )
         .json(&payload)
         .send()
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     if !resp.status().is_success() {
         let status = resp.status();
         let text = resp.text().await.unwrap_or_default();
-        return Err(format!("GitHub API error {}: {}", status, text));
+        return Err(GitError::new(
+            GitErrorKind::GitHub,
+            format!("GitHub API error {}: {}", status, text),
+        ));
     }
 
-    let json: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+    let json: serde_json::Value = resp.json().await?;
     Ok(json["body"].as_str().unwrap_or("").to_string())
 }
 
@@ -178,7 +445,7 @@ pub async fn create_github_release(
     body: String,
     prerelease: bool,
     draft: bool,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let client = Client::new();
 
     let payload = serde_json::json!({
@@ -203,17 +470,39 @@ pub async fn create_github_release(
         )
         .json(&payload)
         .send()
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     if !resp.status().is_success() {
         let status = resp.status();
         let text = resp.text().await.unwrap_or_default();
-        return Err(format!("GitHub API error {}: {}", status, text));
+        return Err(GitError::new(
+            GitErrorKind::GitHub,
+            format!("GitHub API error {}: {}", status, text),
+        ));
     }
 
-    let json: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+    let json: serde_json::Value = resp.json().await?;
     let url = json["html_url"].as_str().unwrap_or("").to_string();
 
     Ok(url)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tag_refs_prefers_peeled_commit() {
+        let out = "aaa\trefs/tags/v1.0\n\
+                   bbb\trefs/tags/v1.1\n\
+                   ccc\trefs/tags/v1.1^{}\n\
+                   ddd\trefs/heads/main\n";
+        assert_eq!(
+            parse_tag_refs(out),
+            [
+                ("v1.0".to_string(), "aaa".to_string()),
+                ("v1.1".to_string(), "ccc".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use crate::cache::get_cache;
+use crate::commands::error::{GitError, GitErrorKind, GitResult};
+use crate::commands::git::{git_run, git_run_with_stdin};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// Event emitted after a debounced batch of changes in a watched repository.
+pub const REPO_CHANGED_EVENT: &str = "repo-changed";
+
+/// Quiet period that ends a batch of filesystem events.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+/// A batch is flushed after this long even if events keep arriving
+/// (long checkouts, builds writing into the tree).
+const MAX_BATCH_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeCategory {
+    /// Files in the working tree (not ignored)
+    Worktree,
+    /// `.git/index`: something was staged, unstaged or committed
+    Index,
+    /// Branches, tags, remote-tracking refs or `packed-refs`
+    Refs,
+    /// `.git/HEAD`: checkout or detach
+    Head,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoChangedEvent {
+    pub repo_path: String,
+    pub categories: Vec<ChangeCategory>,
+    /// Changed working-tree paths, relative to the repository root
+    pub paths: Vec<String>,
+}
+
+/// Where a filesystem event landed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Change {
+    Worktree(String),
+    Git(ChangeCategory),
+}
+
+/// Layout of a watched repository. `git_dirs` holds the git dir and, for
+/// linked worktrees, the common dir where refs live.
+struct RepoLayout {
+    worktree: PathBuf,
+    git_dirs: Vec<PathBuf>,
+}
+
+impl RepoLayout {
+    fn classify(&self, path: &Path) -> Option<Change> {
+        for git_dir in &self.git_dirs {
+            if let Ok(rel) = path.strip_prefix(git_dir) {
+                return classify_git_path(rel).map(Change::Git);
+            }
+        }
+        self.relative(path).map(Change::Worktree)
+    }
+
+    /// `path` relative to the working tree, with the forward slashes git
+    /// uses in cache keys. None for the root itself and outside paths.
+    fn relative(&self, path: &Path) -> Option<String> {
+        let rel = path.strip_prefix(&self.worktree).ok()?;
+        if rel.as_os_str().is_empty() {
+            return None;
+        }
+        Some(
+            rel.components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+}
+
+/// Only the files that change what the UI shows; objects, logs and lock
+/// files are noise (a lock is renamed onto its target when git is done).
+fn classify_git_path(rel: &Path) -> Option<ChangeCategory> {
+    let rel = rel.to_string_lossy().replace('\\', "/");
+    if rel.ends_with(".lock") {
+        return None;
+    }
+    match rel.as_str() {
+        "index" => Some(ChangeCategory::Index),
+        "HEAD" => Some(ChangeCategory::Head),
+        "packed-refs" | "refs" => Some(ChangeCategory::Refs),
+        _ if rel.starts_with("refs/") => Some(ChangeCategory::Refs),
+        _ => None,
+    }
+}
+
+/// The notify watcher with the working-tree directories it watches. Each
+/// directory gets its own non-recursive watch so ignored trees (build
+/// output, node_modules) cost no inotify watches and cause no churn.
+struct WatchSet {
+    watcher: RecommendedWatcher,
+    dirs: BTreeSet<PathBuf>,
+}
+
+struct RepoWatcher {
+    /// Dropping the watcher closes its channel, which ends the debounce
+    /// thread; that thread only keeps a weak reference.
+    _watches: Arc<Mutex<WatchSet>>,
+}
+
+static WATCHERS: OnceLock<Mutex<HashMap<String, RepoWatcher>>> = OnceLock::new();
+
+fn watchers() -> &'static Mutex<HashMap<String, RepoWatcher>> {
+    WATCHERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// `--git-common-dir` may print a path relative to `repo_path`.
+fn rev_parse_path(repo_path: &str, flag: &str) -> GitResult<PathBuf> {
+    let out = git_run(repo_path, &["rev-parse", flag])?;
+    if !out.success {
+        return Err(GitError::from_output(&out));
+    }
+    let path = Path::new(repo_path).join(out.stdout.trim());
+    // notify reports canonical paths (symlinked /tmp on macOS …)
+    Ok(path.canonicalize().unwrap_or(path))
+}
+
+fn watch_error(e: notify::Error) -> GitError {
+    GitError::new(
+        GitErrorKind::Io,
+        format!("Could not watch repository: {}", e),
+    )
+}
+
+/// Drop the paths `.gitignore` (and `info/exclude`, core.excludesFile)
+/// excludes. On failure nothing is filtered.
+fn filter_ignored(repo_path: &str, paths: BTreeSet<String>) -> Vec<String> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+    let input: String = paths.iter().map(|p| format!("{}\0", p)).collect();
+    // Exits 1 when no path is ignored
+    let ignored: BTreeSet<String> =
+        match git_run_with_stdin(repo_path, &["check-ignore", "--stdin", "-z"], &input) {
+            Ok(out) => out
+                .stdout
+                .split('\0')
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect(),
+            Err(_) => BTreeSet::new(),
+        };
+    paths.into_iter().filter(|p| !ignored.contains(p)).collect()
+}
+
+/// Non-ignored directories of the working tree, the root included. Walked
+/// level by level so each level takes a single `check-ignore` run; `.git`
+/// and symlinked directories are not entered.
+fn worktree_dirs(repo_path: &str, layout: &RepoLayout) -> BTreeSet<PathBuf> {
+    let mut dirs = BTreeSet::new();
+    let mut level = vec![layout.worktree.clone()];
+    while !level.is_empty() {
+        let mut children = BTreeSet::new();
+        for dir in level {
+            if let Ok(entries) = std::fs::read_dir(&dir) {
+                for entry in entries.flatten() {
+                    if entry.file_name() == ".git" || !entry.file_type().is_ok_and(|t| t.is_dir()) {
+                        continue;
+                    }
+                    children.extend(layout.relative(&entry.path()));
+                }
+            }
+            dirs.insert(dir);
+        }
+        level = filter_ignored(repo_path, children)
+            .into_iter()
+            .map(|rel| layout.worktree.join(rel))
+            .collect();
+    }
+    dirs
+}
+
+/// A directory that disappeared before its watch was added is not an error.
+fn is_missing_path(e: &notify::Error) -> bool {
+    match &e.kind {
+        notify::ErrorKind::PathNotFound => true,
+        notify::ErrorKind::Io(io) => io.kind() == std::io::ErrorKind::NotFound,
+        _ => false,
+    }
+}
+
+/// Bring the watched directories in line with the working tree: watch new
+/// non-ignored directories, drop ones that were removed or became ignored.
+fn sync_watches(repo_path: &str, layout: &RepoLayout, set: &mut WatchSet) -> GitResult<()> {
+    let dirs = worktree_dirs(repo_path, layout);
+    for stale in set.dirs.difference(&dirs) {
+        // Watches on deleted directories are already gone
+        let _ = set.watcher.unwatch(stale);
+    }
+    set.dirs.retain(|d| dirs.contains(d));
+    for dir in dirs {
+        if set.dirs.contains(&dir) {
+            continue;
+        }
+        match set.watcher.watch(&dir, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                set.dirs.insert(dir);
+            }
+            Err(e) if is_missing_path(&e) => {}
+            Err(e) => return Err(watch_error(e)),
+        }
+    }
+    Ok(())
+}
+
+/// Whether a batch may have changed which directories should be watched:
+/// a non-ignored directory appeared or went away, or a `.gitignore` changed.
+fn needs_sync(layout: &RepoLayout, event: &RepoChangedEvent, set: &WatchSet) -> bool {
+    event.paths.iter().any(|rel| {
+        let path = layout.worktree.join(rel);
+        rel == ".gitignore"
+            || rel.ends_with("/.gitignore")
+            || path.is_dir() != set.dirs.contains(&path)
+    })
+}
+
+/// Drop cached diffs the batch made stale. The unstaged diff compares the
+/// index with the working tree, the staged diff HEAD with the index.
+fn invalidate_cache(repo_path: &str, event: &RepoChangedEvent) {
+    let has = |c| event.categories.contains(&c);
+    let cache = get_cache();
+    if has(ChangeCategory::Index) {
+        cache.invalidate_where(repo_path, |_, _| true);
+        return;
+    }
+    if has(ChangeCategory::Head) || has(ChangeCategory::Refs) {
+        cache.invalidate_where(repo_path, |_, staged| staged);
+    }
+    if !event.paths.is_empty() {
+        // A changed directory (rename, rm -r) covers everything below it
+        cache.invalidate_where(repo_path, |file_path, staged| {
+            !staged
+                && event.paths.iter().any(|p| {
+                    file_path == p
+                        || file_path
+                            .strip_prefix(p.as_str())
+                            .is_some_and(|rest| rest.starts_with('/'))
+                })
+        });
+    }
+}
+
+/// Turn one batch of raw events into a `repo-changed` payload, or None if
+/// nothing relevant changed.
+fn collect_batch(
+    repo_path: &str,
+    layout: &RepoLayout,
+    events: Vec<Event>,
+) -> Option<RepoChangedEvent> {
+    let mut categories = BTreeSet::new();
+    let mut paths = BTreeSet::new();
+    for event in events {
+        if matches!(event.kind, EventKind::Access(_)) {
+            continue;
+        }
+        for path in &event.paths {
+            match layout.classify(path) {
+                Some(Change::Git(category)) => {
+                    categories.insert(category);
+                }
+                Some(Change::Worktree(rel)) => {
+                    paths.insert(rel);
+                }
+                None => {}
+            }
+        }
+    }
+
+    let paths = filter_ignored(repo_path, paths);
+    if !paths.is_empty() {
+        categories.insert(ChangeCategory::Worktree);
+    }
+    if categories.is_empty() {
+        return None;
+    }
+    Some(RepoChangedEvent {
+        repo_path: repo_path.to_string(),
+        categories: categories.into_iter().collect(),
+        paths,
+    })
+}
+
+fn run_debouncer(
+    app: tauri::AppHandle,
+    repo_path: String,
+    layout: RepoLayout,
+    watches: Weak<Mutex<WatchSet>>,
+    rx: Receiver<notify::Result<Event>>,
+) {
+    // Block for the first event of a batch, then gather until things go quiet
+    while let Ok(first) = rx.recv() {
+        let started = Instant::now();
+        let mut batch: Vec<Event> = first.into_iter().collect();
+        loop {
+            let remaining = MAX_BATCH_DELAY.saturating_sub(started.elapsed());
+            if remaining.is_zero() {
+                break;
+            }
+            match rx.recv_timeout(DEBOUNCE.min(remaining)) {
+                Ok(Ok(event)) => batch.push(event),
+                Ok(Err(_)) => {}
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        if let Some(event) = collect_batch(&repo_path, &layout, batch) {
+            if let Some(watches) = watches.upgrade() {
+                if let Ok(mut set) = watches.lock() {
+                    if needs_sync(&layout, &event, &set) {
+                        if let Err(e) = sync_watches(&repo_path, &layout, &mut set) {
+                            eprintln!("Failed to update watches for {}: {}", repo_path, e);
+                        }
+                    }
+                }
+            }
+            invalidate_cache(&repo_path, &event);
+            let _ = app.emit(REPO_CHANGED_EVENT, event);
+        }
+    }
+}
+
+/// Start watching a repository's non-ignored working-tree directories and
+/// its git dir, emitting debounced `repo-changed` events and invalidating
+/// stale cached diffs.
+/// Watching an already watched repository is a no-op.
+#[tauri::command]
+pub fn watch_repo(app: tauri::AppHandle, repo_path: String) -> GitResult<()> {
+    let mut map = watchers()
+        .lock()
+        .map_err(|_| GitError::from("Watcher registry lock poisoned"))?;
+    if map.contains_key(&repo_path) {
+        return Ok(());
+    }
+
+    let worktree = rev_parse_path(&repo_path, "--show-toplevel")?;
+    let mut git_dirs = vec![rev_parse_path(&repo_path, "--absolute-git-dir")?];
+    let common_dir = rev_parse_path(&repo_path, "--git-common-dir")?;
+    if !git_dirs.contains(&common_dir) {
+        git_dirs.push(common_dir);
+    }
+
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .map_err(watch_error)?;
+    // Only what `classify_git_path` looks at: objects and logs are noise
+    for dir in &git_dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(watch_error)?;
+        let refs = dir.join("refs");
+        if refs.is_dir() {
+            watcher
+                .watch(&refs, RecursiveMode::Recursive)
+                .map_err(watch_error)?;
+        }
+    }
+
+    let layout = RepoLayout { worktree, git_dirs };
+    let mut set = WatchSet {
+        watcher,
+        dirs: BTreeSet::new(),
+    };
+    sync_watches(&repo_path, &layout, &mut set)?;
+    let watches = Arc::new(Mutex::new(set));
+
+    let thread_repo = repo_path.clone();
+    let thread_watches = Arc::downgrade(&watches);
+    std::thread::spawn(move || run_debouncer(app, thread_repo, layout, thread_watches, rx));
+    map.insert(repo_path, RepoWatcher { _watches: watches });
+    Ok(())
+}
+
+/// Stop watching a repository. Unknown paths are ignored.
+#[tauri::command]
+pub fn unwatch_repo(repo_path: String) -> GitResult<()> {
+    let mut map = watchers()
+        .lock()
+        .map_err(|_| GitError::from("Watcher registry lock poisoned"))?;
+    map.remove(&repo_path);
+    Ok(())
+}
+
+/// Repositories currently being watched.
+#[tauri::command]
+pub fn list_watched_repos() -> GitResult<Vec<String>> {
+    let map = watchers()
+        .lock()
+        .map_err(|_| GitError::from("Watcher registry lock poisoned"))?;
+    let mut repos: Vec<String> = map.keys().cloned().collect();
+    repos.sort();
+    Ok(repos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_paths() {
+        let layout = RepoLayout {
+            worktree: PathBuf::from("/r"),
+            git_dirs: vec![PathBuf::from("/r/.git")],
+        };
+        let classify = |p: &str| layout.classify(Path::new(p));
+        assert_eq!(
+            classify("/r/src/main.rs"),
+            Some(Change::Worktree("src/main.rs".to_string()))
+        );
+        assert_eq!(
+            classify("/r/.git/index"),
+            Some(Change::Git(ChangeCategory::Index))
+        );
+        assert_eq!(
+            classify("/r/.git/HEAD"),
+            Some(Change::Git(ChangeCategory::Head))
+        );
+        assert_eq!(
+            classify("/r/.git/refs/heads/feature/x"),
+            Some(Change::Git(ChangeCategory::Refs))
+        );
+        assert_eq!(
+            classify("/r/.git/packed-refs"),
+            Some(Change::Git(ChangeCategory::Refs))
+        );
+        assert_eq!(classify("/r/.git/index.lock"), None);
+        assert_eq!(classify("/r/.git/refs/heads/main.lock"), None);
+        assert_eq!(classify("/r/.git/objects/ab/cdef"), None);
+        assert_eq!(classify("/r"), None);
+        assert_eq!(classify("/elsewhere/file"), None);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::commands::error::{GitError, GitResult};
 use crate::commands::git::git_run;
 use serde::{Deserialize, Serialize};
 
@@ -71,10 +72,10 @@ fn parse_worktrees(output: &str) -> Vec<WorktreeInfo> {
 }
 
 #[tauri::command]
-pub async fn list_worktrees(repo_path: String) -> Result<Vec<WorktreeInfo>, String> {
+pub async fn list_worktrees(repo_path: String) -> GitResult<Vec<WorktreeInfo>> {
     let out = git_run(&repo_path, &["worktree", "list", "--porcelain"])?;
     if !out.success && !out.stderr.is_empty() {
-        return Err(out.stderr.trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(parse_worktrees(&out.stdout))
 }
@@ -85,7 +86,7 @@ pub async fn add_worktree(
     path: String,
     branch: String,
     new_branch: bool,
-) -> Result<String, String> {
+) -> GitResult<String> {
     let mut args = vec!["worktree", "add"];
     let flag;
     if new_branch {
@@ -101,17 +102,13 @@ pub async fn add_worktree(
     let args_slice: Vec<&str> = args;
     let out = git_run(&repo_path, &args_slice)?;
     if !out.success {
-        return Err(format!("{}\n{}", out.stdout, out.stderr).trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(format!("Worktree added at {path}"))
 }
 
 #[tauri::command]
-pub async fn remove_worktree(
-    repo_path: String,
-    path: String,
-    force: bool,
-) -> Result<String, String> {
+pub async fn remove_worktree(repo_path: String, path: String, force: bool) -> GitResult<String> {
     let path_ref = path.as_str();
     let args: Vec<&str> = if force {
         vec!["worktree", "remove", "--force", path_ref]
@@ -120,7 +117,7 @@ pub async fn remove_worktree(
     };
     let out = git_run(&repo_path, &args)?;
     if !out.success {
-        return Err(format!("{}\n{}", out.stdout, out.stderr).trim().to_string());
+        return Err(GitError::from_output(&out));
     }
     Ok(format!("Worktree removed: {path}"))
 }
@@ -2,24 +2,48 @@ mod cache;
 mod commands;
 
 use commands::{
-    branch::{create_branch, delete_branch, get_branches, switch_branch},
+    branch::{
+        compare_branches, create_branch, delete_branch, delete_branches, get_branches,
+        get_cleanup_candidates, get_remote_branches, rename_branch, set_branch_upstream,
+        switch_branch,
+    },
+    changelog::generate_changelog,
+    commit_message::{build_commit_message, lint_commit_message, parse_conventional_commit},
     diff::{
-        clear_diff_cache, get_blame, get_cache_stats, get_commit_diff, get_diff, get_diff_batch,
-        get_diff_cached, get_file_content, get_file_log, get_log, invalidate_diff,
-        preload_visible_diffs,
+        clear_diff_cache, get_blame, get_cache_stats, get_commit_diff, get_commit_diff_summary,
+        get_commit_file_diffs, get_diff, get_diff_batch, get_diff_cached, get_file_content,
+        get_file_log, get_log, invalidate_diff, preload_visible_diffs,
     },
     git::git_version,
+    graph::get_graph_log,
+    history::{get_commit_details, search_history},
+    hooks::list_hooks,
+    hunks::{discard_hunks, stage_hunks, unstage_hunks},
+    merge::{
+        abort_repo_operation, cherry_pick, continue_repo_operation, get_conflict_blocks,
+        get_repo_state, list_conflicts, merge_branch, rebase_onto, resolve_conflict_blocks,
+        resolve_conflict_side, resolve_conflict_with_content, revert_commits, skip_repo_operation,
+    },
     oauth::{github_poll_device_token, github_start_device_flow},
-    remote::{create_github_repo, fetch, get_remotes, pull, push},
+    operations::{cancel_operation, list_operations},
+    rebase::{get_rebase_plan, interactive_rebase},
+    release::suggest_next_version,
+    remote::{
+        add_remote, create_github_repo, fetch, get_remotes, pull, push, remove_remote,
+        rename_remote, set_remote_prune, set_remote_url,
+    },
     repo::{
         amend_commit, clone_repo, commit, discard_file_changes, get_current_branch,
         get_last_commit_message, get_status, stage_files, unstage_files,
     },
     stash::{list_stashes, stash_apply, stash_drop, stash_pop, stash_push},
+    status::get_status_snapshot,
     tags::{
         create_github_release, create_tag, delete_remote_tag, delete_tag,
-        generate_github_release_notes, get_commits_since_tag, list_tags, push_tag,
+        generate_github_release_notes, get_commits_since_tag, list_remote_tags, list_tags,
+        push_tag, verify_tag,
     },
+    watcher::{list_watched_repos, unwatch_repo, watch_repo},
     worktree::{add_worktree, list_worktrees, remove_worktree},
 };
 
@@ -37,26 +61,67 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             git_version,
             get_status,
+            get_status_snapshot,
+            watch_repo,
+            unwatch_repo,
+            list_watched_repos,
             stage_files,
             unstage_files,
+            stage_hunks,
+            unstage_hunks,
+            discard_hunks,
             commit,
             get_current_branch,
             clone_repo,
+            cancel_operation,
+            list_operations,
             get_branches,
+            get_remote_branches,
             switch_branch,
             create_branch,
             delete_branch,
+            rename_branch,
+            set_branch_upstream,
+            compare_branches,
+            get_cleanup_candidates,
+            delete_branches,
+            merge_branch,
+            rebase_onto,
+            get_rebase_plan,
+            interactive_rebase,
+            cherry_pick,
+            revert_commits,
+            get_repo_state,
+            continue_repo_operation,
+            abort_repo_operation,
+            skip_repo_operation,
+            list_conflicts,
+            resolve_conflict_side,
+            resolve_conflict_with_content,
+            get_conflict_blocks,
+            resolve_conflict_blocks,
             push,
             pull,
             fetch,
             get_remotes,
+            add_remote,
+            remove_remote,
+            rename_remote,
+            set_remote_url,
+            set_remote_prune,
             create_github_repo,
             list_worktrees,
             add_worktree,
             remove_worktree,
             get_diff,
             get_commit_diff,
+            get_commit_diff_summary,
+            get_commit_file_diffs,
             get_log,
+            get_graph_log,
+            search_history,
+            get_commit_details,
+            list_hooks,
             get_file_log,
             get_blame,
             get_file_content,
@@ -72,12 +137,19 @@ pub fn run() {
             delete_tag,
             push_tag,
             delete_remote_tag,
+            list_remote_tags,
+            verify_tag,
             get_commits_since_tag,
+            suggest_next_version,
+            generate_changelog,
             generate_github_release_notes,
             create_github_release,
             discard_file_changes,
             amend_commit,
             get_last_commit_message,
+            lint_commit_message,
+            parse_conventional_commit,
+            build_commit_message,
             // ── Phase 1 cache commands ──
             get_diff_cached,
             get_diff_batch,
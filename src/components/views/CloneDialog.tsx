@@ -11,7 +11,7 @@ import { Progress } from "@/components/ui/progress";
 import { ScrollArea } from "@/components/ui/scroll-area";
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { cn } from "@/lib/utils";
-import { git } from "@/lib/git";
+import { git, GitError, type GitProgress, type OperationInfo } from "@/lib/git";
 import { useAuthStore } from "@/store/authStore";
 import { useGitHubRepos, type GitHubRepo } from "@/hooks/useGitHub";
 
@@ -31,6 +31,7 @@ export function CloneDialog({ open, onOpenChange, onCloned }: CloneDialogProps)
   const [cloning, setCloning] = useState(false);
   const [lines, setLines] = useState<string[]>([]);
   const [progress, setProgress] = useState<number | null>(null);
+  const [operationId, setOperationId] = useState<number | null>(null);
   const logRef = useRef<HTMLDivElement>(null);
 
   // Auto-scroll log to bottom
@@ -44,13 +45,18 @@ export function CloneDialog({ open, onOpenChange, onCloned }: CloneDialogProps)
   useEffect(() => {
     if (!cloning) return;
     let unlisten: (() => void) | undefined;
-    listen<string>("clone-progress", (event) => {
-      const line = event.payload;
-      setLines((prev) => [...prev, line]);
-      const m = line.match(/Receiving objects:\s+(\d+)%/);
-      if (m) setProgress(parseInt(m[1], 10));
+    let unlistenStarted: (() => void) | undefined;
+    listen<OperationInfo>("git-operation-started", (event) => {
+      if (event.payload.operation === "clone") setOperationId(event.payload.id);
+    }).then((fn) => { unlistenStarted = fn; });
+    listen<GitProgress>("git-progress", (event) => {
+      const p = event.payload;
+      if (p.operation !== "clone") return;
+      setOperationId(p.operation_id);
+      setLines((prev) => [...prev, p.line]);
+      if (p.phase === "Receiving objects" && p.percent !== null) setProgress(p.percent);
     }).then((fn) => { unlisten = fn; });
-    return () => { unlisten?.(); };
+    return () => { unlisten?.(); unlistenStarted?.(); };
   }, [cloning]);
 
   function reset() {
@@ -93,10 +99,15 @@ export function CloneDialog({ open, onOpenChange, onCloned }: CloneDialogProps)
       reset();
       onOpenChange(false);
     } catch (e) {
-      toast.error(`Clone failed: ${String(e)}`);
+      if (e instanceof GitError && e.kind === "cancelled") {
+        toast.info("Clone cancelled");
+      } else {
+        toast.error(`Clone failed: ${String(e)}`);
+      }
     } finally {
       setCloning(false);
       setProgress(null);
+      setOperationId(null);
     }
   }
 
@@ -257,8 +268,15 @@ export function CloneDialog({ open, onOpenChange, onCloned }: CloneDialogProps)
           <Button
             variant="ghost"
             size="sm"
-            onClick={() => { reset(); onOpenChange(false); }}
-            disabled={cloning}
+            onClick={() => {
+              if (cloning) {
+                if (operationId !== null) git.cancelOperation(operationId).catch(() => {});
+                return;
+              }
+              reset();
+              onOpenChange(false);
+            }}
+            disabled={cloning && operationId === null}
           >
             Cancel
           </Button>
@@ -1,5 +1,5 @@
 import { load, type Store } from "@tauri-apps/plugin-store";
-import { invoke } from "@tauri-apps/api/core";
+import { invoke } from "./git";
 
 // Auth token storage backed by tauri-plugin-store (persisted to app data dir)
 let _authStore: Store | null = null;
@@ -1,4 +1,104 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke as tauriInvoke } from "@tauri-apps/api/core";
+
+export type GitErrorKind =
+  | "git_not_found"
+  | "not_a_repository"
+  | "authentication_failed"
+  | "push_rejected"
+  | "merge_conflict"
+  | "uncommitted_changes"
+  | "nothing_to_commit"
+  | "empty_repository"
+  | "no_upstream"
+  | "network"
+  | "not_found"
+  | "already_exists"
+  | "locked"
+  | "hook_failed"
+  | "lint_failed"
+  | "signing_failed"
+  | "cancelled"
+  | "github"
+  | "io"
+  | "other";
+
+/** A client-side hook that rejected a commit or push. */
+export interface HookFailure {
+  hook: string;
+  exit_code: number;
+  output: string;
+}
+
+export type LintRule =
+  | "header_format"
+  | "type_not_allowed"
+  | "scope_required"
+  | "scope_not_allowed"
+  | "subject_too_long"
+  | "body_leading_blank";
+
+export interface LintIssue {
+  rule: LintRule;
+  message: string;
+}
+
+/** Error thrown by every backend command (mirrors `GitError` in Rust). */
+export class GitError extends Error {
+  kind: GitErrorKind;
+  stderr: string;
+  /** Set when `kind` is `hook_failed` */
+  hook: HookFailure | null;
+  /** Set when `kind` is `lint_failed` */
+  lint: LintIssue[] | null;
+
+  constructor(
+    kind: GitErrorKind,
+    message: string,
+    stderr = "",
+    hook: HookFailure | null = null,
+    lint: LintIssue[] | null = null,
+  ) {
+    super(message);
+    this.name = "GitError";
+    this.kind = kind;
+    this.stderr = stderr;
+    this.hook = hook;
+    this.lint = lint;
+  }
+
+  // Keeps `String(e)` in toasts showing just the message.
+  toString() {
+    return this.message;
+  }
+}
+
+/** A backend `GitError` returned inside a result instead of thrown. */
+export interface GitErrorPayload {
+  kind: GitErrorKind;
+  message: string;
+  stderr: string;
+  hook: HookFailure | null;
+  lint: LintIssue[] | null;
+}
+
+/** `invoke` that rethrows backend errors as `GitError`. */
+export async function invoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
+  try {
+    return await tauriInvoke<T>(cmd, args);
+  } catch (e) {
+    if (e && typeof e === "object" && "kind" in e && "message" in e) {
+      const err = e as {
+        kind: GitErrorKind;
+        message: string;
+        stderr?: string;
+        hook?: HookFailure | null;
+        lint?: LintIssue[] | null;
+      };
+      throw new GitError(err.kind, err.message, err.stderr ?? "", err.hook ?? null, err.lint ?? null);
+    }
+    throw e;
+  }
+}
 
 export interface FileStatus {
   path: string;
@@ -9,10 +109,155 @@ export interface FileStatus {
   original_path?: string;
 }
 
+export type StatusEntryKind = "changed" | "renamed" | "copied" | "unmerged" | "untracked" | "ignored";
+
+export type UnmergedKind =
+  | "both_deleted"
+  | "added_by_us"
+  | "deleted_by_them"
+  | "added_by_them"
+  | "deleted_by_us"
+  | "both_added"
+  | "both_modified";
+
+export interface StatusEntry {
+  path: string;
+  /** Source path of a rename or copy */
+  original_path: string | null;
+  kind: StatusEntryKind;
+  /** "" when unchanged */
+  index_status: string;
+  worktree_status: string;
+  /** Rename/copy similarity in percent */
+  similarity: number | null;
+  /** Octal modes such as "100644"; null for untracked/ignored paths */
+  modes: { head: string; index: string; worktree: string } | null;
+  submodule: {
+    commit_changed: boolean;
+    has_tracked_changes: boolean;
+    has_untracked_changes: boolean;
+  } | null;
+  unmerged: UnmergedKind | null;
+}
+
+export interface StatusSnapshot {
+  /** null when HEAD is detached */
+  branch: string | null;
+  /** null before the first commit */
+  head: string | null;
+  upstream: string | null;
+  ahead: number;
+  behind: number;
+  stash_count: number;
+  files: FileStatus[];
+  entries: StatusEntry[];
+}
+
+export interface HookInfo {
+  name: string;
+  path: string;
+  trigger: "commit" | "push" | "merge" | "rebase" | "checkout" | "other";
+  executable: boolean;
+}
+
+export interface HooksInfo {
+  hooks_dir: string;
+  /** `core.hooksPath` is set (husky, lefthook …) */
+  custom_hooks_path: boolean;
+  hooks: HookInfo[];
+}
+
+export interface CommitOptions {
+  signoff?: boolean;
+  /** Sign with GPG/SSH; uses `user.signingkey` unless `signing_key` is set */
+  sign?: boolean;
+  signing_key?: string;
+  /** "Name <email>" */
+  author?: string;
+  /** Author date in any format git accepts */
+  date?: string;
+  allow_empty?: boolean;
+  /** Skip pre-commit and commit-msg hooks */
+  no_verify?: boolean;
+  /** "Name <email>" entries added as Co-authored-by trailers */
+  co_authors?: string[];
+  /** Lint the message as a Conventional Commit first; fails with `lint_failed` */
+  lint?: CommitLintConfig;
+}
+
+/** Omitted fields use the defaults: the builder's types, any scope, 72 chars. */
+export interface CommitLintConfig {
+  /** Empty allows any type */
+  types?: string[];
+  /** Empty allows any scope */
+  scopes?: string[];
+  require_scope?: boolean;
+  max_subject_length?: number;
+}
+
+export interface CommitFooter {
+  token: string;
+  value: string;
+}
+
+export interface ConventionalCommit {
+  type: string;
+  scope?: string | null;
+  breaking: boolean;
+  description: string;
+  body?: string | null;
+  footers: CommitFooter[];
+}
+
 export interface BranchInfo {
   name: string;
   current: boolean;
   upstream?: string;
+  /** Commits not yet on the upstream */
+  ahead: number;
+  /** Upstream commits not yet on this branch */
+  behind: number;
+  /** Upstream is configured but was deleted on the remote */
+  upstream_gone: boolean;
+  commit_hash: string;
+  commit_subject: string;
+  /** ISO 8601 */
+  commit_date: string;
+}
+
+export interface BranchComparison {
+  base: string;
+  head: string;
+  merge_base: string | null;
+  /** Commits on `head` not on `base` (at most 500) */
+  ahead: CommitInfo[];
+  /** Commits on `base` not on `head` (at most 500) */
+  behind: CommitInfo[];
+  ahead_count: number;
+  behind_count: number;
+}
+
+export interface CleanupCandidate extends BranchInfo {
+  /** Fully merged into the chosen base */
+  merged: boolean;
+}
+
+export interface BranchDeleteResult {
+  name: string;
+  deleted: boolean;
+  error: GitErrorPayload | null;
+}
+
+export interface RemoteBranchInfo {
+  /** e.g. "origin/main" */
+  name: string;
+  remote: string;
+  branch: string;
+  commit_hash: string;
+  commit_subject: string;
+  commit_date: string;
+  /** Local branches tracking this one */
+  tracked_by: string[];
 }
 
 export interface WorktreeInfo {
@@ -32,6 +277,108 @@ export interface CommitInfo {
   message: string;
 }
 
+export type RefKind = "head" | "branch" | "remote_branch" | "tag" | "other";
+
+export interface GraphRef {
+  name: string;
+  kind: RefKind;
+  /** Checked-out branch (`HEAD -> name`) */
+  is_current: boolean;
+}
+
+/** Line from a row down to the next row of the graph. */
+export interface GraphEdge {
+  from_lane: number;
+  to_lane: number;
+}
+
+export interface GraphCommit extends CommitInfo {
+  parents: string[];
+  refs: GraphRef[];
+  lane: number;
+  edges: GraphEdge[];
+}
+
+export interface GraphPage {
+  commits: GraphCommit[];
+  next_cursor: string | null;
+}
+
+/** Filters for `searchHistory`; omitted fields are not applied. */
+export interface HistoryQuery {
+  skip?: number;
+  limit?: number;
+  author?: string;
+  since?: string;
+  until?: string;
+  /** Extended regex matched against the commit message */
+  message?: string;
+  ignore_case?: boolean;
+  paths?: string[];
+  follow?: boolean;
+  /** `-S` string, or `-G` regex when `pickaxe_regex` is set */
+  pickaxe?: string;
+  pickaxe_regex?: boolean;
+  revision_range?: string;
+  all_refs?: boolean;
+}
+
+export interface HistoryEntry extends CommitInfo {
+  body: string;
+}
+
+export interface HistoryPage {
+  commits: HistoryEntry[];
+  next_skip: number | null;
+}
+
+export interface CommitPerson {
+  name: string;
+  email: string;
+  /** Unix seconds */
+  timestamp: number;
+  /** ISO 8601 with the original timezone */
+  date: string;
+}
+
+export interface CommitTrailer {
+  key: string;
+  value: string;
+}
+
+export type SignatureStatus =
+  | "unsigned"
+  | "good"
+  | "bad"
+  | "unknown_validity"
+  | "expired"
+  | "expired_key"
+  | "revoked_key"
+  | "cannot_check";
+
+export interface CommitFileStat {
+  path: string;
+  old_path: string | null;
+  /** null for binary files */
+  additions: number | null;
+  deletions: number | null;
+}
+
+export interface CommitDetails {
+  hash: string;
+  short_hash: string;
+  parents: string[];
+  author: CommitPerson;
+  committer: CommitPerson;
+  subject: string;
+  body: string;
+  trailers: CommitTrailer[];
+  signature: { status: SignatureStatus; signer: string | null; key: string | null };
+  files: CommitFileStat[];
+  additions: number;
+  deletions: number;
+}
+
 export interface BlameLine {
   line_number: number;
   hash: string;
@@ -49,14 +396,226 @@ export interface StashInfo {
 
 export interface RemoteInfo {
   name: string;
+  /** Fetch URL */
   url: string;
+  /** Same as `url` unless a separate push URL is set */
+  push_url: string;
+  /** Per-remote prune setting; null falls back to `fetch.prune` */
+  prune: boolean | null;
 }
 
 export interface TagInfo {
   name: string;
+  /** Tagged commit (peeled for annotated tags) */
   commit_hash: string;
   date: string;
   message: string | null;
+  /** False for lightweight tags */
+  annotated: boolean;
+  signed: boolean;
+  tagger: string | null;
+}
+
+export interface TagOptions {
+  /** Defaults to "annotated" */
+  kind?: "lightweight" | "annotated" | "signed";
+  /** Commit to tag; defaults to HEAD */
+  target?: string;
+  /** Key for signed tags; uses `user.signingkey` when unset */
+  signing_key?: string;
+  /** Move an existing tag */
+  force?: boolean;
+}
+
+export interface RemoteTag {
+  name: string;
+  commit_hash: string;
+  exists_locally: boolean;
+  /** The local tag points at a different commit */
+  differs: boolean;
+}
+
+export interface TagVerification {
+  status: SignatureStatus;
+  signer: string | null;
+  key: string | null;
+  output: string;
+}
+
+/** A version parsed from a tag; `prefix` is whatever precedes the digits ("v"). */
+export interface Version {
+  prefix: string;
+  major: number;
+  minor: number;
+  patch: number;
+  pre: string | null;
+}
+
+export type BumpLevel = "none" | "patch" | "minor" | "major";
+
+export interface ReleaseCommit {
+  hash: string;
+  short_hash: string;
+  author: string;
+  date: string;
+  /** null for messages that aren't conventional commits */
+  type: string | null;
+  scope: string | null;
+  description: string;
+  breaking: boolean;
+  breaking_note: string | null;
+}
+
+export interface ChangelogSection {
+  /** Commit type, "breaking" or "other" */
+  key: string;
+  title: string;
+  commits: ReleaseCommit[];
+}
+
+export interface VersionSuggestion {
+  current_tag: string | null;
+  current_version: Version | null;
+  bump: BumpLevel;
+  next_version: Version;
+  next_tag: string;
+  commit_count: number;
+  sections: ChangelogSection[];
+}
+
+export interface ChangelogOptions {
+  /** Previous tag; omitted includes the whole history */
+  from?: string;
+  /** Defaults to HEAD */
+  to?: string;
+  /** Entry heading; defaults to `to` or "Unreleased" */
+  version?: string;
+  /** keep_a_changelog leaves out docs, test, ci, style and chore commits */
+  format?: "markdown" | "keep_a_changelog";
+  /** Prepend the entry to CHANGELOG.md in the repo root */
+  write_file?: boolean;
+}
+
+/** Payload of the `git-progress` event emitted during clone/push/pull/fetch. */
+export interface GitProgress {
+  operation_id: number;
+  operation: "clone" | "push" | "pull" | "fetch" | "commit";
+  phase: string;
+  percent: number | null;
+  current: number | null;
+  total: number | null;
+  throughput: string | null;
+  line: string;
+}
+
+export type ChangeCategory = "worktree" | "index" | "refs" | "head";
+
+/** Payload of the `repo-changed` event emitted by `watchRepo` watchers. */
+export interface RepoChangedEvent {
+  repo_path: string;
+  categories: ChangeCategory[];
+  /** Changed, non-ignored working-tree paths relative to the repo root */
+  paths: string[];
+}
+
+/** Also the payload of the `git-operation-started` event, emitted as soon as
+ *  a cancellable operation's git process is running. */
+export interface OperationInfo {
+  id: number;
+  operation: "clone" | "push" | "pull" | "fetch" | "commit";
+  repo_path: string;
+}
+
+/** Inclusive range of indices into `ParsedHunk.lines`. */
+export interface LineRange {
+  start: number;
+  end: number;
+}
+
+/** Indices into the diff whose `diff_text` is passed along with the
+ *  selection; the backend rejects it if the file changed since. */
+export interface HunkSelection {
+  hunk_index: number;
+  /** Empty = whole hunk */
+  line_ranges: LineRange[];
+}
+
+export type InProgressKind = "merge" | "rebase" | "cherry_pick" | "revert";
+
+/** An in-progress merge/rebase/cherry-pick/revert. */
+export interface RepoState {
+  kind: InProgressKind;
+  head_name: string | null;
+  target: string | null;
+  /** Rebase progress; always null for cherry-picks and reverts. */
+  current_step: number | null;
+  total_steps: number | null;
+  conflicted_files: string[];
+}
+
+export type MergeStatus = "up_to_date" | "fast_forward" | "completed" | "conflicts" | "stopped";
+
+export interface MergeOutcome {
+  status: MergeStatus;
+  message: string;
+  conflicts: string[];
+  state: RepoState | null;
+}
+
+export type RebaseAction = "pick" | "reword" | "squash" | "fixup" | "drop" | "edit";
+
+export interface RebaseTodoItem {
+  action: RebaseAction;
+  commit: CommitInfo;
+  /** New message for `reword`, or for the combined commit on `squash`/`fixup` */
+  message?: string | null;
+}
+
+export type ConflictSide = "ours" | "theirs";
+
+export interface ConflictFile {
+  path: string;
+  conflict_type:
+    | "both_modified"
+    | "both_added"
+    | "deleted_by_us"
+    | "deleted_by_them"
+    | "added_by_us"
+    | "added_by_them"
+    | "both_deleted";
+  base: string | null;
+  ours: string | null;
+  theirs: string | null;
+  working: string | null;
+}
+
+/** One `<<<<<<<` … `>>>>>>>` region; line numbers are 1-based. */
+export interface ConflictBlock {
+  index: number;
+  start_line: number;
+  end_line: number;
+  ours_label: string;
+  base_label: string | null;
+  theirs_label: string;
+  ours: string;
+  /** Only with `merge.conflictStyle=diff3` */
+  base: string | null;
+  theirs: string;
+}
+
+export type BlockChoice =
+  | "ours"
+  | "theirs"
+  | "base"
+  | "ours_then_theirs"
+  | "theirs_then_ours"
+  | "custom";
+
+export interface BlockResolution {
+  block_index: number;
+  choice: BlockChoice;
+  /** Required for `custom` */
+  content?: string | null;
 }
 
 export const git = {
@@ -66,20 +625,53 @@ export const git = {
   getStatus: (repoPath: string) =>
     invoke<FileStatus[]>("get_status", { repoPath }),
 
+  getStatusSnapshot: (repoPath: string) =>
+    invoke<StatusSnapshot>("get_status_snapshot", { repoPath }),
+
+  watchRepo: (repoPath: string) =>
+    invoke<void>("watch_repo", { repoPath }),
+
+  unwatchRepo: (repoPath: string) =>
+    invoke<void>("unwatch_repo", { repoPath }),
+
+  listWatchedRepos: () =>
+    invoke<string[]>("list_watched_repos"),
+
   stageFiles: (repoPath: string, paths: string[]) =>
     invoke<void>("stage_files", { repoPath, paths }),
 
   unstageFiles: (repoPath: string, paths: string[]) =>
     invoke<void>("unstage_files", { repoPath, paths }),
 
+  stageHunks: (repoPath: string, filePath: string, diffText: string, selection: HunkSelection[]) =>
+    invoke<void>("stage_hunks", { repoPath, filePath, diffText, selection }),
+
+  unstageHunks: (repoPath: string, filePath: string, diffText: string, selection: HunkSelection[]) =>
+    invoke<void>("unstage_hunks", { repoPath, filePath, diffText, selection }),
+
+  discardHunks: (repoPath: string, filePath: string, diffText: string, selection: HunkSelection[]) =>
+    invoke<void>("discard_hunks", { repoPath, filePath, diffText, selection }),
+
   discardFileChanges: (repoPath: string, paths: string[], isUntracked: boolean) =>
     invoke<void>("discard_file_changes", { repoPath, paths, isUntracked }),
 
-  commit: (repoPath: string, message: string) =>
-    invoke<string>("commit", { repoPath, message }),
+  commit: (repoPath: string, message: string, options?: CommitOptions) =>
+    invoke<string>("commit", { repoPath, message, options: options ?? null }),
+
+  amendCommit: (repoPath: string, message: string, options?: CommitOptions) =>
+    invoke<string>("amend_commit", { repoPath, message, options: options ?? null }),
+
+  listHooks: (repoPath: string) =>
+    invoke<HooksInfo>("list_hooks", { repoPath }),
+
+  lintCommitMessage: (message: string, config?: CommitLintConfig) =>
+    invoke<LintIssue[]>("lint_commit_message", { message, config: config ?? null }),
+
+  parseConventionalCommit: (message: string) =>
+    invoke<ConventionalCommit | null>("parse_conventional_commit", { message }),
 
-  amendCommit: (repoPath: string, message: string) =>
-    invoke<string>("amend_commit", { repoPath, message }),
+  buildCommitMessage: (commit: ConventionalCommit) =>
+    invoke<string>("build_commit_message", { commit }),
 
   getLastCommitMessage: (repoPath: string) =>
     invoke<string>("get_last_commit_message", { repoPath }),
@@ -90,6 +682,9 @@ export const git = {
   getBranches: (repoPath: string) =>
     invoke<BranchInfo[]>("get_branches", { repoPath }),
 
+  getRemoteBranches: (repoPath: string) =>
+    invoke<RemoteBranchInfo[]>("get_remote_branches", { repoPath }),
+
   switchBranch: (repoPath: string, name: string) =>
     invoke<void>("switch_branch", { repoPath, name }),
 
@@ -99,18 +694,60 @@ export const git = {
   deleteBranch: (repoPath: string, name: string, force: boolean) =>
     invoke<void>("delete_branch", { repoPath, name, force }),
 
-  push: (repoPath: string, setUpstream = false) =>
-    invoke<string>("push", { repoPath, setUpstream }),
+  /** Works for the current branch too; `force` overwrites `newName`. */
+  renameBranch: (repoPath: string, oldName: string, newName: string, force = false) =>
+    invoke<void>("rename_branch", { repoPath, oldName, newName, force }),
+
+  /** null unsets the upstream. */
+  setBranchUpstream: (repoPath: string, branch: string, upstream: string | null) =>
+    invoke<void>("set_branch_upstream", { repoPath, branch, upstream }),
+
+  compareBranches: (repoPath: string, base: string, head: string) =>
+    invoke<BranchComparison>("compare_branches", { repoPath, base, head }),
+
+  /** Branches merged into `base` or with a deleted upstream. */
+  getCleanupCandidates: (repoPath: string, base: string) =>
+    invoke<CleanupCandidate[]>("get_cleanup_candidates", { repoPath, base }),
+
+  deleteBranches: (repoPath: string, names: string[], force = false) =>
+    invoke<BranchDeleteResult[]>("delete_branches", { repoPath, names, force }),
+
+  /** Without `remote` this is a plain `git push` (origin when `setUpstream`). */
+  push: (repoPath: string, setUpstream = false, remote?: string, refspec?: string) =>
+    invoke<string>("push", { repoPath, setUpstream, remote: remote ?? null, refspec: refspec ?? null }),
 
-  pull: (repoPath: string) =>
-    invoke<string>("pull", { repoPath }),
+  pull: (repoPath: string, remote?: string, refspec?: string) =>
+    invoke<string>("pull", { repoPath, remote: remote ?? null, refspec: refspec ?? null }),
 
-  fetch: (repoPath: string) =>
-    invoke<string>("fetch", { repoPath }),
+  /** Fetches all remotes when `remote` is omitted; `prune` overrides the config. */
+  fetch: (repoPath: string, remote?: string, refspec?: string, prune?: boolean) =>
+    invoke<string>("fetch", {
+      repoPath,
+      remote: remote ?? null,
+      refspec: refspec ?? null,
+      prune: prune ?? null,
+    }),
 
   getRemotes: (repoPath: string) =>
     invoke<RemoteInfo[]>("get_remotes", { repoPath }),
 
+  addRemote: (repoPath: string, name: string, url: string, pushUrl?: string) =>
+    invoke<void>("add_remote", { repoPath, name, url, pushUrl: pushUrl ?? null }),
+
+  removeRemote: (repoPath: string, name: string) =>
+    invoke<void>("remove_remote", { repoPath, name }),
+
+  renameRemote: (repoPath: string, oldName: string, newName: string) =>
+    invoke<void>("rename_remote", { repoPath, oldName, newName }),
+
+  /** With `push`, a null `url` removes the separate push URL. */
+  setRemoteUrl: (repoPath: string, name: string, url: string | null, push = false) =>
+    invoke<void>("set_remote_url", { repoPath, name, url, push }),
+
+  /** null clears the per-remote setting so `fetch.prune` applies. */
+  setRemotePrune: (repoPath: string, name: string, prune: boolean | null) =>
+    invoke<void>("set_remote_prune", { repoPath, name, prune }),
+
   createGithubRepo: (
     repoPath: string,
     token: string,
@@ -146,6 +783,16 @@ export const git = {
   getLog: (repoPath: string, limit = 100) =>
     invoke<CommitInfo[]>("get_log", { repoPath, limit }),
 
+  getCommitDetails: (repoPath: string, hash: string) =>
+    invoke<CommitDetails>("get_commit_details", { repoPath, hash }),
+
+  searchHistory: (repoPath: string, query: HistoryQuery) =>
+    invoke<HistoryPage>("search_history", { repoPath, query }),
+
+  /** Pass the previous page's `next_cursor` to load more. */
+  getGraphLog: (repoPath: string, cursor: string | null = null, pageSize = 200, allBranches = true) =>
+    invoke<GraphPage>("get_graph_log", { repoPath, cursor, pageSize, allBranches }),
+
   getFileLog: (repoPath: string, filePath: string) =>
     invoke<CommitInfo[]>("get_file_log", { repoPath, filePath }),
 
@@ -175,21 +822,37 @@ export const git = {
   listTags: (repoPath: string) =>
     invoke<TagInfo[]>("list_tags", { repoPath }),
 
-  createTag: (repoPath: string, name: string, message: string) =>
-    invoke<string>("create_tag", { repoPath, name, message }),
+  /** `message` is ignored for lightweight tags. */
+  createTag: (repoPath: string, name: string, message: string, options?: TagOptions) =>
+    invoke<string>("create_tag", { repoPath, name, message, options: options ?? null }),
 
   deleteTag: (repoPath: string, name: string) =>
     invoke<string>("delete_tag", { repoPath, name }),
 
-  pushTag: (repoPath: string, tagName: string) =>
-    invoke<string>("push_tag", { repoPath, tagName }),
+  /** `remote` defaults to origin. */
+  pushTag: (repoPath: string, tagName: string, remote?: string) =>
+    invoke<string>("push_tag", { repoPath, tagName, remote: remote ?? null }),
+
+  deleteRemoteTag: (repoPath: string, tagName: string, remote?: string) =>
+    invoke<string>("delete_remote_tag", { repoPath, tagName, remote: remote ?? null }),
+
+  listRemoteTags: (repoPath: string, remote?: string) =>
+    invoke<RemoteTag[]>("list_remote_tags", { repoPath, remote: remote ?? null }),
 
-  deleteRemoteTag: (repoPath: string, tagName: string) =>
-    invoke<string>("delete_remote_tag", { repoPath, tagName }),
+  verifyTag: (repoPath: string, name: string) =>
+    invoke<TagVerification>("verify_tag", { repoPath, name }),
 
   getCommitsSinceTag: (repoPath: string, tag?: string) =>
     invoke<string[]>("get_commits_since_tag", { repoPath, tag: tag ?? null }),
 
+  /** `prerelease` is an identifier such as "rc" to suggest `v1.3.0-rc.1`. */
+  suggestNextVersion: (repoPath: string, prerelease?: string) =>
+    invoke<VersionSuggestion>("suggest_next_version", { repoPath, prerelease: prerelease ?? null }),
+
+  /** Build a changelog entry locally, without GitHub. Returns the Markdown. */
+  generateChangelog: (repoPath: string, options: ChangelogOptions = {}) =>
+    invoke<string>("generate_changelog", { repoPath, options }),
+
   generateGithubReleaseNotes: (
     token: string,
     owner: string,
@@ -229,4 +892,79 @@ export const git = {
   // Clone
   cloneRepo: (url: string, destPath: string) =>
     invoke<string>("clone_repo", { url, destPath }),
+
+  // Merge / rebase
+  mergeBranch: (repoPath: string, branch: string, noFf = false, message?: string) =>
+    invoke<MergeOutcome>("merge_branch", { repoPath, branch, noFf, message: message ?? null }),
+
+  rebaseOnto: (repoPath: string, upstream: string) =>
+    invoke<MergeOutcome>("rebase_onto", { repoPath, upstream }),
+
+  /** Commits in `base..HEAD`, oldest first, as an all-pick plan. */
+  getRebasePlan: (repoPath: string, base: string) =>
+    invoke<RebaseTodoItem[]>("get_rebase_plan", { repoPath, base }),
+
+  interactiveRebase: (repoPath: string, base: string, plan: RebaseTodoItem[]) =>
+    invoke<MergeOutcome>("interactive_rebase", { repoPath, base, plan }),
+
+  /** `commits` may mix single revisions and `A..B` ranges. */
+  cherryPick: (
+    repoPath: string,
+    commits: string[],
+    options: { recordOrigin?: boolean; noCommit?: boolean; mainline?: number } = {}
+  ) =>
+    invoke<MergeOutcome>("cherry_pick", {
+      repoPath,
+      commits,
+      recordOrigin: options.recordOrigin ?? false,
+      noCommit: options.noCommit ?? false,
+      mainline: options.mainline ?? null,
+    }),
+
+  revertCommits: (
+    repoPath: string,
+    commits: string[],
+    options: { noCommit?: boolean; mainline?: number } = {}
+  ) =>
+    invoke<MergeOutcome>("revert_commits", {
+      repoPath,
+      commits,
+      noCommit: options.noCommit ?? false,
+      mainline: options.mainline ?? null,
+    }),
+
+  getRepoState: (repoPath: string) =>
+    invoke<RepoState | null>("get_repo_state", { repoPath }),
+
+  continueRepoOperation: (repoPath: string) =>
+    invoke<MergeOutcome>("continue_repo_operation", { repoPath }),
+
+  abortRepoOperation: (repoPath: string) =>
+    invoke<void>("abort_repo_operation", { repoPath }),
+
+  skipRepoOperation: (repoPath: string) =>
+    invoke<MergeOutcome>("skip_repo_operation", { repoPath }),
+
+  listConflicts: (repoPath: string) =>
+    invoke<ConflictFile[]>("list_conflicts", { repoPath }),
+
+  resolveConflictSide: (repoPath: string, path: string, side: ConflictSide) =>
+    invoke<void>("resolve_conflict_side", { repoPath, path, side }),
+
+  resolveConflictWithContent: (repoPath: string, path: string, content: string) =>
+    invoke<void>("resolve_conflict_with_content", { repoPath, path, content }),
+
+  getConflictBlocks: (repoPath: string, path: string) =>
+    invoke<ConflictBlock[]>("get_conflict_blocks", { repoPath, path }),
+
+  /** Returns the blocks still unresolved; the file is staged once none remain. */
+  resolveConflictBlocks: (repoPath: string, path: string, resolutions: BlockResolution[]) =>
+    invoke<ConflictBlock[]>("resolve_conflict_blocks", { repoPath, path, resolutions }),
+
+  // Long-running operations
+  listOperations: () =>
+    invoke<OperationInfo[]>("list_operations"),
+
+  cancelOperation: (id: number) =>
+    invoke<void>("cancel_operation", { id }),
 };
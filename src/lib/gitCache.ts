@@ -1,4 +1,4 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke, type CommitFileStat, type GitErrorPayload } from "./git";
 
 /**
  * Phase 1: Cache-aware diff operations
@@ -10,6 +10,7 @@ import { invoke } from "@tauri-apps/api/core";
  *   - getDiffCached() → similar to getDiff() but with cache hits (<1ms)
  *   - getDiffBatch() → fetch 10+ files in one IPC call
  *   - preloadVisibleDiffs() → background preload for visible files
+ *   - getCommitDiffSummary() + getCommitFileDiffs() → commit diffs, file by file
  */
 
 export interface DiffResult {
@@ -20,15 +21,49 @@ export interface DiffResult {
   from_cache: boolean;
 }
 
+export interface DiffLine {
+  kind: "context" | "added" | "removed";
+  old_line: number | null;
+  new_line: number | null;
+  content: string;
+  no_newline: boolean;
+}
+
 export interface ParsedHunk {
   header: string;
+  old_start: number;
+  old_lines: number;
+  new_start: number;
+  new_lines: number;
   added_lines: number;
   removed_lines: number;
   context_lines: number;
+  lines: DiffLine[];
 }
 
+export type FileChangeKind =
+  | "added"
+  | "deleted"
+  | "modified"
+  | "renamed"
+  | "copied"
+  | "mode_changed";
+
 export interface ParsedFileDiff {
+  /** New path, or the old path for deletions */
   file_path: string;
+  /** null for added files */
+  old_path: string | null;
+  /** null for deleted files */
+  new_path: string | null;
+  change: FileChangeKind;
+  /** Rename/copy similarity in percent */
+  similarity: number | null;
+  old_mode: string | null;
+  new_mode: string | null;
+  /** Binary files have no hunks */
+  binary: boolean;
+  header: string;
   hunks: ParsedHunk[];
   added_lines: number;
   removed_lines: number;
@@ -48,6 +83,21 @@ export interface DiffBatchResult {
   total_from_compute: number;
 }
 
+/** Result of `getCommitFileDiffs`: files that failed are listed in `errors`. */
+export interface CommitFileDiffs extends DiffBatchResult {
+  errors: { file_path: string; error: GitErrorPayload }[];
+}
+
+/** File list of a commit diff; patches are loaded per file. */
+export interface CommitDiffSummary {
+  /** Resolved base (the empty tree for root commits) */
+  base: string;
+  target: string;
+  files: CommitFileStat[];
+  additions: number;
+  deletions: number;
+}
+
 export interface CacheStats {
   total_entries: number;
   memory_used_kb: number;
@@ -109,6 +159,47 @@ export async function getDiffBatch(
   });
 }
 
+/**
+ * Files changed by a commit (against its first parent), or between
+ * `base` and `target` when a base is given. No patch text is sent.
+ */
+export async function getCommitDiffSummary(
+  repoPath: string,
+  target: string,
+  base?: string
+): Promise<CommitDiffSummary> {
+  return invoke<CommitDiffSummary>("get_commit_diff_summary", {
+    repoPath,
+    target,
+    base: base ?? null,
+  });
+}
+
+/**
+ * Parsed diffs for some files of a commit (or `base`..`target` range)
+ *
+ * Load only what is on screen; results are cached permanently since
+ * commits never change.
+ *
+ * Example:
+ *   const summary = await getCommitDiffSummary(repoPath, hash);
+ *   const visible = summary.files.slice(0, 20);
+ *   const { diffs, errors } = await getCommitFileDiffs(repoPath, hash, visible);
+ */
+export async function getCommitFileDiffs(
+  repoPath: string,
+  target: string,
+  files: Pick<CommitFileStat, "path" | "old_path">[],
+  base?: string
+): Promise<CommitFileDiffs> {
+  return invoke<CommitFileDiffs>("get_commit_file_diffs", {
+    repoPath,
+    target,
+    base: base ?? null,
+    files,
+  });
+}
+
 /**
  * Preload diffs for visible files in background
  * 